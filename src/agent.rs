@@ -1,183 +1,187 @@
+use std::sync::Arc;
 use std::sync::mpsc;
-use rand::Rng;
-use num_enum::TryFromPrimitive;
+use std::time::Duration;
+use std::time::Instant;
 
-use crate::common::CellList;
+use crate::board::Action;
 use crate::board::Player;
 use crate::board::Board;
-use crate::referee::Referee;
+use crate::evaluation;
+use crate::minimax;
+use crate::strategy;
+use crate::strategy::Parameter;
+use crate::strategy::RequestKind;
+use crate::strategy::SearchContext;
+use crate::strategy::StopFlags;
+use crate::strategy::Strategy;
 
 type Move = (usize, usize);
 
 // Message-passing types
+// every request carries a unique, increasing id, so that responses can be matched with their requests
 pub struct MoveRequest {
 
+    pub request_id: u64,
     pub board: Board,
     pub player: Player,
     pub pace_response: bool,
-    pub algorithm_choice: AiType,
-    pub recursion_depth: usize,
+    // an index into the strategy registry
+    pub strategy: usize,
+    pub parameters: Vec<Parameter>,
+    // otherwise the agent continues with its own random sequence
+    pub seed: Option<u64>,
 }
 
 pub struct MoveResult {
 
-    pub board: Board,
+    pub request_id: u64,
     pub player: Player,
-    pub next_move: Move,
+    pub action: Action,
+    pub statistics: SearchStatistics,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, TryFromPrimitive)]
-#[repr(usize)]
-pub enum AiType {
-    Random,
-    Minimax,
+// how the agent came up with a move
+#[derive(Clone)]
+pub struct SearchStatistics {
+
+    pub node_count: u64,
+    // not counting the pace
+    pub duration: Duration,
+    // the deepest completed search, zero if the strategy doesn't search in depth
+    pub depth: usize,
+    // the evaluation of the move, if the strategy evaluates moves
+    pub score: Option<f32>,
+    pub principal_variation: Vec<Action>,
 }
 
-pub struct Agent {
-    rng: rand::prelude::ThreadRng,
-    move_request_receiver: mpsc::Receiver<MoveRequest>,
-    move_result_sender: mpsc::Sender<MoveResult>,
-    valid_moves: CellList,
-    referee: Referee,
-}
-
-impl Agent {
-
-    pub fn new(move_request_receiver: mpsc::Receiver<MoveRequest>, move_result_sender: mpsc::Sender<MoveResult>) -> Self {
-
-        Agent {
-            rng: rand::rng(),
-            move_request_receiver: move_request_receiver,
-            move_result_sender: move_result_sender,
-            valid_moves: CellList::default(),
-            referee: Referee::default(),
-        }
-    }
-
-    pub fn run(&mut self) {
-
-        while let Ok(move_request) = self.move_request_receiver.recv() {
+impl SearchStatistics {
 
-            let next_move = match move_request.algorithm_choice {
-                AiType::Random => self.find_random_move(&move_request.board, move_request.player),
-                AiType::Minimax => self.find_best_move_using_minimax(&move_request.board, move_request.player, move_request.recursion_depth),
-            };
+    pub fn nodes_per_second(&self) -> f64 {
 
-            if move_request.pace_response {
-                std::thread::sleep(std::time::Duration::from_secs(1));
-            }
-            
-            self.move_result_sender.send(MoveResult { board: move_request.board, player: move_request.player, next_move }).unwrap();
-        }
+        let seconds = self.duration.as_secs_f64();
+        if seconds > 0.0 { self.node_count as f64 / seconds } else { 0.0 }
     }
+}
 
-    // returns a random valid move
-    fn find_random_move(&mut self, board: &Board, player: Player) -> Move {
-
-        if self.referee.find_all_valid_moves(board, player, &mut self.valid_moves) {
-
-            self.valid_moves.list[self.rng.random_range(..self.valid_moves.count)]
-
-        } else {
-
-            (Board::SIZE, Board::SIZE)
-        }
-    }
+// asks for the best few moves of a position rather than just the one to play
+pub struct AnalysisRequest {
 
-    // uses an algorithm that will try to find a move that maximizes oneself and minimizes the opponent
-    fn find_best_move_using_minimax(&mut self, board: &Board, player: Player, recursion_depth: usize) -> Move {
+    pub request_id: u64,
+    pub board: Board,
+    pub player: Player,
+    pub recursion_depth: usize,
+    pub line_count: usize,
+    pub seed: Option<u64>,
+}
 
-        let mut allocation_count = 0;
-        let (optimal_move, _optimal_score) = self.find_best_move_recursive(board, player, recursion_depth, &mut allocation_count);
-        optimal_move
-    }
+#[derive(Clone)]
+pub struct AnalysisLine {
 
-    // returns (the optimal move, evaluation score given to that move)
-    // TODO: add alpha-beta pruning
-    // TODO: it's silly to think very hard about the first few moves
-    fn find_best_move_recursive(&mut self, board: &Board, player: Player, recursion_depth: usize, allocation_count: &mut i32) -> (Move, f32) {
-        let mut optimal_move = (Board::SIZE, Board::SIZE);
-        let mut optimal_score = f32::NEG_INFINITY;
-        let mut selection_count = 0; // Track number of equally good moves found
+    pub next_move: Move,
+    pub score: f32,
+    // the expected continuation, starting with next_move, with the passes along the way
+    pub principal_variation: Vec<Action>,
+}
 
-        let mut row = 0;
-        let mut col = 0;
-        while row < Board::SIZE {
-            while col < Board::SIZE {
+pub struct AnalysisResult {
 
-                let mut new_board = board.clone();
+    pub request_id: u64,
+    pub board: Board,
+    pub player: Player,
+    // sorted from best to worst
+    pub lines: Vec<AnalysisLine>,
+}
 
-                (row, col) = self.referee.find_and_apply_next_valid_move(&mut new_board, player, (row, col));
+pub enum AgentRequest {
+    Move(MoveRequest),
+    Analysis(AnalysisRequest),
+}
 
-                if row < Board::SIZE && col < Board::SIZE {
+// sent repeatedly while the agent is searching for a move
+pub struct ThinkingReport {
 
-                    *allocation_count += 1;
+    pub request_id: u64,
+    // the depth currently being searched
+    pub depth: usize,
+    pub node_count: u64,
+    pub nodes_per_second: f64,
+    // the best line of the deepest completed search, if any
+    pub best_line: Option<AnalysisLine>,
+}
 
-                    // the evaluated score of this potential move is...
-                    let board_score = 
-                        // ...(depending on how far we want to think into the future)...
-                        if recursion_depth == 1 {
+// boxed, since a principal variation or a board makes them a lot larger than they would otherwise be
+pub enum AgentResponse {
+    Move(Box<MoveResult>),
+    Analysis(Box<AnalysisResult>),
+    Thinking(Box<ThinkingReport>),
+}
 
-                            // ...either how good it would make the board for us now...
-                            self.evaluate_board(&new_board, player)
+pub struct Agent {
+    request_receiver: mpsc::Receiver<AgentRequest>,
+    response_sender: mpsc::Sender<AgentResponse>,
+    // the registry of strategies, indexed by MoveRequest::strategy
+    strategies: Vec<Box<dyn Strategy>>,
+    search: SearchContext,
+}
 
-                        } else {
+impl Agent {
 
-                            // ...or how good the board will become if the opponent makes their best move next...
-                            let (_opponent_move, opponent_score) = self.find_best_move_recursive(&new_board, player.opponent(), recursion_depth - 1, allocation_count);
+    pub fn new(request_receiver: mpsc::Receiver<AgentRequest>, response_sender: mpsc::Sender<AgentResponse>, stop_flags: Arc<StopFlags>, seed: u64) -> Self {
 
-                            // ...and since this is a symmetric, zero-sum game,
-                            // how good it is for us is the inverse of how good it is for them
-                            -opponent_score
-                        };
+        Agent {
+            request_receiver,
+            response_sender: response_sender.clone(),
+            strategies: strategy::registry(),
+            search: SearchContext::new(response_sender, stop_flags, seed),
+        }
+    }
 
-                    if optimal_move == (Board::SIZE, Board::SIZE) {
+    pub fn run(&mut self) {
 
-                        // any move is better than no move
-                        optimal_score = board_score;
-                        optimal_move = (row, col);
+        while let Ok(request) = self.request_receiver.recv() {
 
-                        selection_count = 1;
+            let response = match request {
+                AgentRequest::Move(move_request) => AgentResponse::Move(Box::new(self.handle_move_request(move_request))),
+                AgentRequest::Analysis(analysis_request) => AgentResponse::Analysis(Box::new(self.handle_analysis_request(analysis_request))),
+            };
 
-                    } else if board_score == optimal_score {
+            // the receiving end is gone if the game is shutting down
+            if self.response_sender.send(response).is_err() {
+                break;
+            }
+        }
+    }
 
-                        // online reservoir sampling ensures equally good moves have equal chance of getting picked
-                        selection_count += 1;
-                        let replacement_probability = 1.0 / selection_count as f64;
-                        if self.rng.random_bool(replacement_probability) {
+    fn handle_move_request(&mut self, move_request: MoveRequest) -> MoveResult {
 
-                            optimal_score = board_score;
-                            optimal_move = (row, col);
-                        }
+        self.search.begin_request(RequestKind::Move, move_request.request_id, move_request.seed);
 
-                    } else if board_score > optimal_score {
+        let strategy = &mut self.strategies[move_request.strategy];
+        let action = strategy.choose_move(&mut self.search, &move_request.board, move_request.player, &move_request.parameters);
+        let statistics = self.search.statistics();
 
-                        // this is for sure the best move so far
-                        optimal_score = board_score;
-                        optimal_move = (row, col);
-                        
-                        selection_count = 1;
-                    }
-                }
+        if move_request.pace_response {
 
-                col += 1;
+            // keep an eye on the stop flag, so that moving now doesn't have to wait for the pace
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_secs(1) && !self.search.is_stopped() {
+                std::thread::sleep(Duration::from_millis(10));
             }
-            
-            row += 1;
-            col = 0;
         }
 
-        (optimal_move, optimal_score)
+        self.search.end_request();
+
+        MoveResult { request_id: move_request.request_id, player: move_request.player, action, statistics }
     }
 
-    // for now, the evaluation is only based on the number of pieces
-    // TODO: add heuristics, such as strong positions
-    // TODO: add end-of-game awareness
-    fn evaluate_board(&mut self, board: &Board, player: Player) -> f32 {
+    fn handle_analysis_request(&mut self, analysis_request: AnalysisRequest) -> AnalysisResult {
+
+        let AnalysisRequest { request_id, board, player, recursion_depth, line_count, seed } = analysis_request;
 
-        let (player_count, opponent_count) = Referee::count_disks(board, player);
-        let score = player_count as f32 - opponent_count as f32;
+        self.search.begin_request(RequestKind::Analysis, request_id, seed);
+        let lines = minimax::analyze(&mut self.search, &board, player, recursion_depth, line_count, &evaluation::DEFAULT_WEIGHTS);
+        self.search.end_request();
 
-        score
+        AnalysisResult { request_id, board, player, lines }
     }
-}
\ No newline at end of file
+}
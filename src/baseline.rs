@@ -0,0 +1,212 @@
+use rand::Rng;
+
+use crate::board::Action;
+use crate::board::Player;
+use crate::board::Board;
+use crate::board::Cell;
+use crate::board::Topology;
+use crate::common::CellList;
+use crate::evaluation;
+use crate::evaluation::Region;
+use crate::referee::Referee;
+use crate::strategy::Parameter;
+use crate::strategy::SearchContext;
+use crate::strategy::Strategy;
+
+type Move = (usize, usize);
+
+// Simple, classic strategies, which are weak but make for meaningful reference opponents
+// under misère rules, they turn their notion of a good disc around, the way the evaluation does
+
+// plays any valid move
+pub struct RandomStrategy;
+
+impl Strategy for RandomStrategy {
+
+    fn name(&self) -> &'static str {
+
+        "Random"
+    }
+
+    fn choose_move(&mut self, search: &mut SearchContext, board: &Board, player: Player, _parameters: &[Parameter]) -> Action {
+
+        if search.referee.find_all_valid_moves(board, player, &mut search.valid_moves) {
+
+            Action::Place(search.valid_moves.get(search.rng.random_range(..search.valid_moves.count)))
+
+        } else {
+
+            Action::Pass
+        }
+    }
+}
+
+// plays the valid move with the highest score, where score is given (search, board after the move, move, flip count)
+// equally good moves have equal chance of getting picked, and without a valid move it passes
+fn find_greedy_move(search: &mut SearchContext, board: &Board, player: Player, score: impl Fn(&mut SearchContext, &Board, Move, usize) -> i32) -> Action {
+
+    let mut optimal_move = None;
+    let mut optimal_score = i32::MIN;
+    let mut selection_count = 0;
+
+    search.referee.find_all_valid_moves(board, player, &mut search.valid_moves);
+    let valid_moves = search.valid_moves.clone();
+    let mut flip_cells = CellList::default();
+
+    for next_move in valid_moves.iter() {
+
+        search.count_node();
+
+        let mut new_board = board.clone();
+        search.referee.find_flip_cells_for_move(board, player, next_move, &mut flip_cells);
+        Referee::apply_move(&mut new_board, player, next_move, &flip_cells);
+
+        let move_score = score(search, &new_board, next_move, flip_cells.count);
+
+        if move_score > optimal_score {
+
+            optimal_score = move_score;
+            optimal_move = Some(next_move);
+            selection_count = 1;
+
+        } else if move_score == optimal_score {
+
+            // online reservoir sampling, as in the minimax search
+            selection_count += 1;
+            if search.rng.random_bool(1.0 / selection_count as f64) {
+
+                optimal_move = Some(next_move);
+            }
+        }
+    }
+
+    optimal_move.map_or(Action::Pass, Action::Place)
+}
+
+// flips as many disks as possible, or as few under misère rules
+pub struct GreedyStrategy;
+
+impl Strategy for GreedyStrategy {
+
+    fn name(&self) -> &'static str {
+
+        "Greedy"
+    }
+
+    fn choose_move(&mut self, search: &mut SearchContext, board: &Board, player: Player, _parameters: &[Parameter]) -> Action {
+
+        let objective = evaluation::objective(board) as i32;
+        find_greedy_move(search, board, player, |_search, _new_board, _next_move, flip_count| objective * flip_count as i32)
+    }
+}
+
+// leaves the opponent with as few valid moves as possible, whatever the rules, since mobility matters either way
+pub struct MobilityStrategy;
+
+impl Strategy for MobilityStrategy {
+
+    fn name(&self) -> &'static str {
+
+        "Min Mobility"
+    }
+
+    fn choose_move(&mut self, search: &mut SearchContext, board: &Board, player: Player, _parameters: &[Parameter]) -> Action {
+
+        find_greedy_move(search, board, player, |search, new_board, _next_move, _flip_count| {
+
+            search.referee.find_all_valid_moves(new_board, new_board.next_player(player), &mut search.valid_moves);
+            -(search.valid_moves.count as i32)
+        })
+    }
+}
+
+// takes corners whenever possible and avoids the squares that give corners away, otherwise plays randomly
+// under misère rules, it's the other way around
+pub struct CornerStrategy;
+
+impl Strategy for CornerStrategy {
+
+    fn name(&self) -> &'static str {
+
+        "Corners"
+    }
+
+    fn choose_move(&mut self, search: &mut SearchContext, board: &Board, player: Player, _parameters: &[Parameter]) -> Action {
+
+        let objective = evaluation::objective(board) as i32;
+        find_greedy_move(search, board, player, |_search, _new_board, (row, col), _flip_count| {
+
+            objective * match evaluation::classify(board, (row, col)) {
+
+                Region::Corner => 1,
+                // an X-square is only dangerous as long as its corner is free
+                Region::XSquare => {
+
+                    let nearest = |i: usize| if i < board.size() / 2 { 0 } else { board.size() - 1 };
+                    if board.grid[nearest(row)][nearest(col)] == Cell::Empty { -1 } else { 0 }
+                }
+                _ => 0,
+            }
+        })
+    }
+}
+
+// the classic positional table for 8x8, given by the distances to the nearest edges, so that it fits any size
+//  100 -20  10   5   5  10 -20 100
+//  -20 -50  -2  -2  -2  -2 -50 -20
+//   10  -2  -1  -1  -1  -1  -2  10
+//    5  -2  -1  -1  -1  -1  -2   5
+//   ...
+// on a torus, every cell is worth the same
+fn positional_value(board: &Board, (row, col): Move) -> i32 {
+
+    if board.topology == Topology::Torus {
+
+        return 1;
+    }
+
+    let last = board.size() - 1;
+    let distance = |i: usize| i.min(last - i);
+    let (near, far) = (distance(row).min(distance(col)), distance(row).max(distance(col)));
+
+    match (near, far) {
+        (0, 0) => 100,
+        (0, 1) => -20,
+        (1, 1) => -50,
+        (0, 2) => 10,
+        (0, _) => 5,
+        (1, _) => -2,
+        _ => -1,
+    }
+}
+
+// maximizes the positional value of its disks minus that of the opponent's disks, or minimizes it under misère rules
+pub struct PositionalStrategy;
+
+impl Strategy for PositionalStrategy {
+
+    fn name(&self) -> &'static str {
+
+        "Positional"
+    }
+
+    fn choose_move(&mut self, search: &mut SearchContext, board: &Board, player: Player, _parameters: &[Parameter]) -> Action {
+
+        let objective = evaluation::objective(board) as i32;
+        find_greedy_move(search, board, player, |_search, new_board, _next_move, _flip_count| {
+
+            let mut score = 0;
+            for row in 0..new_board.size() {
+                for col in 0..new_board.size() {
+
+                    match new_board.grid[row][col] {
+                        Cell::Empty | Cell::Blocked => {},
+                        Cell::Taken(owner) if owner == player => score += positional_value(new_board, (row, col)),
+                        Cell::Taken(_) => score -= positional_value(new_board, (row, col)),
+                    }
+                }
+            }
+            objective * score
+        })
+    }
+}
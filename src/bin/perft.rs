@@ -0,0 +1,68 @@
+// Counts the leaf nodes of the game tree from the start position, for verifying move generation
+// usage: perft [--depth <n>] [--representation bitboard|grid] [--divide]
+// with --divide, the counts are broken down by the first move
+
+use std::time::Instant;
+
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::nboard;
+use othello::perft;
+use othello::referee::Referee;
+use othello::referee::Representation;
+
+fn main() {
+
+    let mut arguments = std::env::args().skip(1);
+
+    let mut depth: usize = 9;
+    let mut representation = Representation::BitBoard;
+    let mut divide = false;
+
+    while let Some(option) = arguments.next() {
+
+        if option == "--divide" {
+
+            divide = true;
+            continue;
+        }
+
+        let value = arguments.next().unwrap_or_else(|| exit_with_usage(&format!("missing value for {option}")));
+
+        match option.as_str() {
+            "--depth" => depth = value.parse().unwrap_or_else(|_| exit_with_usage(&format!("invalid value for {option}: {value}"))),
+            "--representation" => representation = match value.as_str() {
+                "bitboard" => Representation::BitBoard,
+                "grid" => Representation::Grid,
+                _ => exit_with_usage(&format!("invalid value for {option}: {value}")),
+            },
+            _ => exit_with_usage(&format!("unknown option {option}")),
+        }
+    }
+
+    let mut referee = Referee::with_representation(representation);
+    let board = Board::default();
+
+    if divide {
+
+        for (first_move, leaf_count) in perft::divide(&mut referee, &board, Player::Black, depth) {
+
+            println!("{} {leaf_count}", nboard::format_move(Action::Place(first_move)));
+        }
+    }
+
+    for current_depth in 1..=depth {
+
+        let start = Instant::now();
+        let leaf_count = perft::perft(&mut referee, &board, Player::Black, current_depth);
+        println!("Depth {current_depth}: {leaf_count} leaves in {:.3}s", start.elapsed().as_secs_f64());
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+
+    eprintln!("{message}");
+    eprintln!("usage: perft [--depth <n>] [--representation bitboard|grid] [--divide]");
+    std::process::exit(1);
+}
@@ -0,0 +1,58 @@
+// A tiny NBoard engine that always plays the first valid move it finds
+// used for testing the external engine support, but it can be played against as well
+// with --hang, it never answers go, like an engine stuck thinking
+
+use std::io::BufRead;
+use std::io::Write;
+
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::common::CellList;
+use othello::nboard;
+use othello::referee::Referee;
+
+fn main() {
+
+    let mut referee = Referee::default();
+    let mut valid_moves = CellList::default();
+    let mut position: Option<(Board, Player)> = None;
+    let hangs = std::env::args().skip(1).any(|argument| argument == "--hang");
+
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    for line in stdin.lock().lines() {
+
+        let Ok(line) = line else {
+            break;
+        };
+
+        let (command, arguments) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+
+        match command {
+
+            "ping" => {
+                let _ = writeln!(stdout, "pong {arguments}");
+            }
+            "set" => {
+                if let Some(game) = arguments.strip_prefix("game ") {
+                    position = nboard::parse_game(game);
+                }
+            }
+            "go" if hangs => {}
+            "go" => {
+                let action = match &position {
+                    Some((board, player)) if referee.find_all_valid_moves(board, *player, &mut valid_moves) => Action::Place(valid_moves.get(0)),
+                    _ => Action::Pass,
+                };
+                let _ = writeln!(stdout, "=== {}", nboard::format_move(action));
+            }
+            "quit" => break,
+            // nboard, move, hint, learn and the like are not needed for a stub
+            _ => {}
+        }
+
+        let _ = stdout.flush();
+    }
+}
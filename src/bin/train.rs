@@ -0,0 +1,81 @@
+// Trains the evaluation weights by self-play, saving checkpoints along the way
+// usage: train [--weights <path>] [--games <n>] [--checkpoint-interval <n>] [--seed <n>] [--resume]
+// without --resume, training starts over from the default weights
+
+use std::path::PathBuf;
+
+use othello::evaluation;
+use othello::learning::Checkpoint;
+use othello::learning::Trainer;
+use othello::learning::TrainingConfig;
+
+fn main() {
+
+    let mut arguments = std::env::args().skip(1);
+
+    let mut path = PathBuf::from("weights.txt");
+    let mut game_count: u64 = 10000;
+    let mut checkpoint_interval: u64 = 1000;
+    let mut seed: u64 = rand::random::<u32>() as u64;
+    let mut resume = false;
+
+    while let Some(option) = arguments.next() {
+
+        if option == "--resume" {
+
+            resume = true;
+            continue;
+        }
+
+        let value = arguments.next().unwrap_or_else(|| exit_with_usage(&format!("missing value for {option}")));
+        let number = || value.parse::<u64>().unwrap_or_else(|_| exit_with_usage(&format!("invalid value for {option}: {value}")));
+
+        match option.as_str() {
+            "--weights" => path = PathBuf::from(&value),
+            "--games" => game_count = number(),
+            "--checkpoint-interval" => checkpoint_interval = number().max(1),
+            "--seed" => seed = number(),
+            _ => exit_with_usage(&format!("unknown option {option}")),
+        }
+    }
+
+    let checkpoint = if resume {
+
+        Checkpoint::load(&path).unwrap_or_else(|e| exit_with_usage(&format!("unable to resume from {}: {e}", path.display())))
+
+    } else {
+
+        Checkpoint::default()
+    };
+
+    println!("Training for {game_count} games from game {}, with seed {seed}", checkpoint.game_count);
+
+    let mut trainer = Trainer::new(checkpoint, TrainingConfig::default(), seed);
+    let mut black_score_sum = 0;
+
+    for game in 1..=game_count {
+
+        black_score_sum += trainer.train_game();
+
+        if game % checkpoint_interval == 0 || game == game_count {
+
+            if let Err(e) = trainer.checkpoint.save(&path) {
+
+                eprintln!("Unable to save checkpoint to {}: {e}", path.display());
+                std::process::exit(1);
+            }
+
+            let weights: Vec<String> = evaluation::FEATURE_NAMES.iter().zip(trainer.checkpoint.weights)
+                .map(|(name, weight)| format!("{name} {weight:.3}"))
+                .collect();
+            println!("Game {}: {}, average result for black {:+.1}", trainer.checkpoint.game_count, weights.join(", "), black_score_sum as f64 / game as f64);
+        }
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+
+    eprintln!("{message}");
+    eprintln!("usage: train [--weights <path>] [--games <n>] [--checkpoint-interval <n>] [--seed <n>] [--resume]");
+    std::process::exit(1);
+}
@@ -0,0 +1,83 @@
+// Tunes the parameters of a strategy by self-play
+// usage: tune [--strategy <name>] [--depth <n>] [--population <n>] [--generations <n>] [--games <n>] [--openings <n>] [--seed <n>]
+// all integer parameters but the depth are tuned
+
+use othello::strategy;
+use othello::strategy::ParameterValue;
+use othello::tuning;
+use othello::tuning::TuningConfig;
+
+fn main() {
+
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+
+    let mut strategy_name = "Minimax".to_string();
+    let mut depth = None;
+    let mut config = TuningConfig::default();
+
+    for pair in arguments.chunks(2) {
+
+        let [option, value] = pair else {
+            exit_with_usage(&format!("missing value for {}", pair[0]));
+        };
+
+        let number = || value.parse::<usize>().unwrap_or_else(|_| exit_with_usage(&format!("invalid value for {option}: {value}")));
+
+        match option.as_str() {
+            "--strategy" => strategy_name = value.clone(),
+            "--depth" => depth = Some(number() as i32),
+            "--population" => config.population_size = number(),
+            "--generations" => config.generation_count = number(),
+            "--games" => config.games_per_match = number(),
+            "--openings" => config.opening_move_count = number(),
+            "--seed" => config.seed = number() as u64,
+            _ => exit_with_usage(&format!("unknown option {option}")),
+        }
+    }
+
+    let strategies = strategy::registry();
+    config.strategy = strategies.iter().position(|strategy| strategy.name() == strategy_name)
+        .unwrap_or_else(|| exit_with_usage(&format!("unknown strategy {strategy_name}")));
+
+    config.base_parameters = strategies[config.strategy].parameters();
+    for (i, parameter) in config.base_parameters.iter_mut().enumerate() {
+
+        if let ParameterValue::Integer { value, min, max } = &mut parameter.value {
+
+            if parameter.name == "Depth" {
+
+                if let Some(depth) = depth {
+                    *value = depth.clamp(*min, *max);
+                }
+
+            } else {
+
+                config.tuned_parameters.push(i);
+            }
+        }
+    }
+
+    if config.tuned_parameters.is_empty() {
+
+        exit_with_usage(&format!("{strategy_name} has no parameters to tune"));
+    }
+
+    let result = tuning::tune(&config, |generation, win_rate, parameters| {
+
+        let label = strategies[config.strategy].label(parameters);
+        println!("Generation {}: {:.1}% wins with {label}", generation + 1, win_rate * 100.0);
+    });
+
+    println!("Best parameters, winning {:.1}% against the defaults:", result.win_rate * 100.0);
+    for parameter in &result.parameters {
+
+        println!("  {parameter}");
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+
+    eprintln!("{message}");
+    eprintln!("usage: tune [--strategy <name>] [--depth <n>] [--population <n>] [--generations <n>] [--games <n>] [--openings <n>] [--seed <n>]");
+    std::process::exit(1);
+}
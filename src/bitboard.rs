@@ -0,0 +1,200 @@
+use crate::board::Board;
+use crate::board::Cell;
+use crate::board::Player;
+use crate::board::Topology;
+
+type Move = (usize, usize);
+
+// A board as 64-bit masks, one per player and one for the blocked cells, where bit row * 8 + col is set for a taken cell
+// moves are generated and flipped by shifting whole masks at a time, instead of visiting cell by cell
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct BitBoard {
+
+    pub black: u64,
+    pub white: u64,
+    pub blocked: u64,
+}
+
+// the bitboard only supports the standard board size, one bit per cell
+const _: () = assert!(BitBoard::SIZE == Board::STANDARD_SIZE);
+
+// masks out the cells that would have wrapped around to the other side of the board
+const NOT_FIRST_COLUMN: u64 = 0xfefefefefefefefe;
+const NOT_LAST_COLUMN: u64 = 0x7f7f7f7f7f7f7f7f;
+
+#[derive(Clone, Copy)]
+enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+const DIRECTIONS: [Direction; 8] = [
+    Direction::North,
+    Direction::NorthEast,
+    Direction::East,
+    Direction::SouthEast,
+    Direction::South,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+];
+
+// moves every cell of the mask one step in the direction
+fn shift(bits: u64, direction: Direction) -> u64 {
+
+    match direction {
+        Direction::North => bits >> 8,
+        Direction::NorthEast => (bits >> 7) & NOT_FIRST_COLUMN,
+        Direction::East => (bits << 1) & NOT_FIRST_COLUMN,
+        Direction::SouthEast => (bits << 9) & NOT_FIRST_COLUMN,
+        Direction::South => bits << 8,
+        Direction::SouthWest => (bits << 7) & NOT_LAST_COLUMN,
+        Direction::West => (bits >> 1) & NOT_LAST_COLUMN,
+        Direction::NorthWest => (bits >> 9) & NOT_LAST_COLUMN,
+    }
+}
+
+pub fn to_bit((row, col): Move) -> u64 {
+
+    1 << (row * BitBoard::SIZE + col)
+}
+
+// the cells of a mask, in row-major order
+pub fn iter_cells(mut bits: u64) -> impl Iterator<Item = Move> {
+
+    std::iter::from_fn(move || {
+
+        if bits == 0 {
+
+            None
+
+        } else {
+
+            let index = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some((index / BitBoard::SIZE, index % BitBoard::SIZE))
+        }
+    })
+}
+
+impl BitBoard {
+
+    pub const SIZE: usize = 8;
+
+    // one mask per player, so two players only, none of the rules of the original Reversi, and a flat board
+    pub fn supports(board: &Board) -> bool {
+
+        board.size() == BitBoard::SIZE && !board.is_rolit() && !board.is_reversi() && board.topology == Topology::Flat
+    }
+
+    pub fn from_board(board: &Board) -> Self {
+
+        debug_assert!(BitBoard::supports(board));
+
+        let mut bit_board = BitBoard::default();
+
+        for row in 0..BitBoard::SIZE {
+            for col in 0..BitBoard::SIZE {
+
+                match board.grid[row][col] {
+                    Cell::Empty => {},
+                    Cell::Blocked => bit_board.blocked |= to_bit((row, col)),
+                    Cell::Taken(Player::Black) => bit_board.black |= to_bit((row, col)),
+                    Cell::Taken(Player::White) => bit_board.white |= to_bit((row, col)),
+                    Cell::Taken(Player::Red | Player::Blue) => {},
+                }
+            }
+        }
+
+        bit_board
+    }
+
+    // returns (player mask, opponent mask)
+    pub fn masks(&self, player: Player) -> (u64, u64) {
+
+        match player {
+            Player::Black => (self.black, self.white),
+            Player::White => (self.white, self.black),
+            Player::Red | Player::Blue => unreachable!("the bitboard only supports black and white"),
+        }
+    }
+
+    pub fn empty(&self) -> u64 {
+
+        !(self.black | self.white | self.blocked)
+    }
+
+    // every empty cell from which a line of opponent disks is closed by a player disk
+    pub fn find_valid_moves(&self, player: Player) -> u64 {
+
+        let (own, opponent) = self.masks(player);
+        let mut valid_moves = 0;
+
+        for direction in DIRECTIONS {
+
+            // opponent disks reachable from the player's disks in this direction,
+            // there can be at most six of them in a row
+            let mut line = shift(own, direction) & opponent;
+            for _ in 0..5 {
+                line |= shift(line, direction) & opponent;
+            }
+
+            valid_moves |= shift(line, direction) & self.empty();
+        }
+
+        valid_moves
+    }
+
+    // the opponent disks flipped by the move, zero if the move is invalid
+    pub fn find_flips(&self, player: Player, next_move: Move) -> u64 {
+
+        let move_bit = to_bit(next_move);
+        if move_bit & self.empty() == 0 {
+
+            return 0;
+        }
+
+        let (own, opponent) = self.masks(player);
+        let mut flips = 0;
+
+        for direction in DIRECTIONS {
+
+            let mut line = 0;
+            let mut cell = shift(move_bit, direction);
+            while cell & opponent != 0 {
+
+                line |= cell;
+                cell = shift(cell, direction);
+            }
+
+            // the line only flips if it's closed by one of the player's disks
+            if cell & own != 0 {
+                flips |= line;
+            }
+        }
+
+        flips
+    }
+
+    pub fn apply_move(&mut self, player: Player, next_move: Move, flips: u64) {
+
+        let changed = to_bit(next_move) | flips;
+        match player {
+            Player::Black => {
+                self.black |= changed;
+                self.white &= !flips;
+            }
+            Player::White => {
+                self.white |= changed;
+                self.black &= !flips;
+            }
+            Player::Red | Player::Blue => unreachable!("the bitboard only supports black and white"),
+        }
+    }
+}
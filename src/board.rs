@@ -1,17 +1,26 @@
 #[derive(Debug, Clone, Copy, PartialEq)]
+// red and blue only take part in Rolit, the multiplayer variant
 pub enum Player {
     Black,
-    White
+    White,
+    Red,
+    Blue,
 }
 
 impl Player {
 
+    pub const COUNT: usize = 4;
+    // in the order of turns
+    pub const ALL: [Player; Player::COUNT] = [Player::Black, Player::White, Player::Red, Player::Blue];
+
+    // only two-player games have a single opponent, see Board::next_player otherwise
     pub fn opponent(&self) -> Self {
 
         match self {
 
             Player::Black => Player::White,
-            Player::White => Player::Black
+            Player::White => Player::Black,
+            Player::Red | Player::Blue => panic!("{self:?} has more than one opponent"),
         }
     }
 }
@@ -20,34 +29,378 @@ impl Player {
 pub enum Cell {
     Empty,
     Taken(Player),
+    // a hole in the board, which can't be played and stops any line through it
+    Blocked,
+}
+
+// what a player does on their turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    // puts a disc on the (row, col) cell
+    Place((usize, usize)),
+    // only allowed without a valid move to place
+    Pass,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Ruleset {
+    #[default]
+    Standard,
+    // anti-Othello, where the player with fewer discs wins
+    Misere,
 }
 
-#[derive(Clone, Debug)]
+impl Ruleset {
+
+    pub fn name(&self) -> &'static str {
+
+        match self {
+            Ruleset::Standard => "Standard",
+            Ruleset::Misere => "Misère",
+        }
+    }
+}
+
+// how the edges of the board connect
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Topology {
+    #[default]
+    Flat,
+    // the edges wrap around to the opposite side, so there are no corners or edges
+    Torus,
+}
+
+impl Topology {
+
+    pub fn name(&self) -> &'static str {
+
+        match self {
+            Topology::Flat => "Flat",
+            Topology::Torus => "Torus",
+        }
+    }
+}
+
+// the grid is large enough for any board size, only the top left size x size cells are in use
+#[derive(Clone, Debug, PartialEq)]
 pub struct Board {
-    pub grid: [[Cell; Board::SIZE]; Board::SIZE],
+    size: usize,
+    pub grid: [[Cell; Board::MAX_SIZE]; Board::MAX_SIZE],
+    pub ruleset: Ruleset,
+    pub topology: Topology,
+    // more than two players play by the Rolit rules
+    player_count: usize,
+    // the discs left to play, indexed by player, if they're limited like in the original Reversi rules
+    supplies: Option<[usize; Player::COUNT]>,
 }
 
 type Move = (usize, usize);
 
 impl Board {
-    pub const SIZE: usize = 8;
+    pub const STANDARD_SIZE: usize = 8;
+    pub const MIN_SIZE: usize = 4;
+    pub const MAX_SIZE: usize = 16;
+
+    // the starting position, with the four discs in the centre
+    // the size must be even and within MIN_SIZE..=MAX_SIZE
+    pub fn new(size: usize) -> Self {
+
+        assert!(size.is_multiple_of(2) && (Board::MIN_SIZE..=Board::MAX_SIZE).contains(&size), "unsupported board size {size}");
+
+        let mut board = Board::empty(size);
+        let centre = size / 2;
+        board.grid[centre - 1][centre - 1] = Cell::Taken(Player::White);
+        board.grid[centre][centre] = Cell::Taken(Player::White);
+        board.grid[centre - 1][centre] = Cell::Taken(Player::Black);
+        board.grid[centre][centre - 1] = Cell::Taken(Player::Black);
+
+        board
+    }
+
+    // the cross start, with each player's discs side by side instead of diagonally
+    pub fn new_parallel(size: usize) -> Self {
+
+        let mut board = Board::new(size);
+        let centre = size / 2;
+        board.grid[centre - 1][centre] = Cell::Taken(Player::White);
+        board.grid[centre][centre] = Cell::Taken(Player::Black);
+
+        board
+    }
+
+    // reads a position written row by row, with X or * for black, O for white, R for red, B for blue,
+    // - or . for empty and # for blocked cells
+    // whitespace is ignored, and the size follows from the number of cells
+    // red or blue discs make it a Rolit board, of four players if there is a blue disc,
+    // since the text doesn't tell whether blue takes turns or only has its starting disc on the board
+    pub fn from_text(text: &str) -> Option<Self> {
+
+        let cells: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let size = (Board::MIN_SIZE..=Board::MAX_SIZE).step_by(2).find(|size| size * size == cells.len())?;
+
+        let mut board = Board::empty(size);
+        for (i, c) in cells.into_iter().enumerate() {
+
+            let cell = match c.to_ascii_uppercase() {
+                '-' | '.' => Cell::Empty,
+                'X' | '*' => Cell::Taken(Player::Black),
+                'O' => Cell::Taken(Player::White),
+                'R' => Cell::Taken(Player::Red),
+                'B' => Cell::Taken(Player::Blue),
+                '#' => Cell::Blocked,
+                _ => return None,
+            };
+            if let Cell::Taken(player) = cell {
+                board.player_count = board.player_count.max(player as usize + 1);
+            }
+            board.grid[i / size][i % size] = cell;
+        }
+
+        Some(board)
+    }
+
+    // the counterpart of from_text, on a single line, like the 64 characters of the Othello test suites
+    pub fn to_text(&self) -> String {
+
+        let mut text = String::with_capacity(self.size * self.size);
+        for row in 0..self.size {
+            for col in 0..self.size {
+
+                text.push(match self.grid[row][col] {
+                    Cell::Empty => '-',
+                    Cell::Taken(Player::Black) => 'X',
+                    Cell::Taken(Player::White) => 'O',
+                    Cell::Taken(Player::Red) => 'R',
+                    Cell::Taken(Player::Blue) => 'B',
+                    Cell::Blocked => '#',
+                });
+            }
+        }
+
+        text
+    }
+
+    // the cells followed by X, O, R or B for the side to move, the way test suites write positions
+    pub fn to_position_text(&self, player: Player) -> String {
+
+        let side = match player {
+            Player::Black => 'X',
+            Player::White => 'O',
+            Player::Red => 'R',
+            Player::Blue => 'B',
+        };
+
+        format!("{} {side}", self.to_text())
+    }
+
+    // reads a position with the side to move, the cells as in from_text followed by X, *, O, R or B
+    pub fn from_position_text(text: &str) -> Option<(Self, Player)> {
+
+        let text = text.trim_end();
+        let side = text.chars().last()?;
+        let player = match side.to_ascii_uppercase() {
+            'X' | '*' => Player::Black,
+            'O' => Player::White,
+            'R' => Player::Red,
+            'B' => Player::Blue,
+            _ => return None,
+        };
+
+        // the side to move takes part even if none of its discs are left
+        let mut board = Board::from_text(&text[..text.len() - side.len_utf8()])?;
+        board.player_count = board.player_count.max(player as usize + 1);
+
+        Some((board, player))
+    }
+
+    // the Rolit start, with one disc of every color in the centre, in the order of turns clockwise
+    // with three players, the fourth color only takes part as a disc on the board
+    pub fn new_rolit(size: usize, player_count: usize) -> Self {
+
+        assert!((3..=Player::COUNT).contains(&player_count), "unsupported player count {player_count}");
+
+        let mut board = Board::new(size);
+        let centre = size / 2;
+        board.grid[centre - 1][centre - 1] = Cell::Taken(Player::Black);
+        board.grid[centre - 1][centre] = Cell::Taken(Player::White);
+        board.grid[centre][centre] = Cell::Taken(Player::Red);
+        board.grid[centre][centre - 1] = Cell::Taken(Player::Blue);
+        board.player_count = player_count;
+
+        board
+    }
+
+    // the original Reversi rules, where the board starts empty and the players fill the centre themselves
+    // half of the cells' worth of discs are given to each player
+    pub fn new_reversi(size: usize) -> Self {
+
+        let mut board = Board::new(size);
+        for (row, col) in board.centre() {
+
+            board.grid[row][col] = Cell::Empty;
+        }
+
+        let supply = size * size / 2;
+        board.supplies = Some([supply, supply, 0, 0]);
+
+        board
+    }
+
+    // blocks the triangles of cells within the given distance of every corner, like an octagon for a distance of a quarter of the size
+    // any disc on those cells is removed
+    pub fn cut_corners(&mut self, distance: usize) {
+
+        let last = self.size - 1;
+        let edge_distance = |i: usize| i.min(last - i);
+
+        for row in 0..self.size {
+            for col in 0..self.size {
+
+                if edge_distance(row) + edge_distance(col) < distance {
+
+                    self.grid[row][col] = Cell::Blocked;
+                }
+            }
+        }
+    }
+
+    pub fn empty(size: usize) -> Self {
+
+        Board {
+            size,
+            grid: [[Cell::Empty; Board::MAX_SIZE]; Board::MAX_SIZE],
+            ruleset: Ruleset::default(),
+            topology: Topology::default(),
+            player_count: 2,
+            supplies: None,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn player_count(&self) -> usize {
+        self.player_count
+    }
+
+    pub fn is_rolit(&self) -> bool {
+        self.player_count > 2
+    }
+
+    // the players taking part, in the order of turns
+    pub fn players(&self) -> impl Iterator<Item = Player> + use<> {
+        Player::ALL.into_iter().take(self.player_count)
+    }
+
+    // the player whose turn it is after the given player's, not taking passes into account
+    pub fn next_player(&self, player: Player) -> Player {
+        Player::ALL[(player as usize + 1) % self.player_count]
+    }
+
+    pub fn is_reversi(&self) -> bool {
+        self.supplies.is_some()
+    }
+
+    // None if the supply is unlimited
+    pub fn supply(&self, player: Player) -> Option<usize> {
+        self.supplies.map(|supplies| supplies[player as usize])
+    }
+
+    // a player who runs out of discs is handed them by the opponent
+    pub fn has_disc(&self, player: Player) -> bool {
+
+        match self.supplies {
+            Some(supplies) => supplies[player as usize] > 0 || supplies[self.next_player(player) as usize] > 0,
+            None => true,
+        }
+    }
+
+    pub fn take_disc(&mut self, player: Player) {
+
+        let opponent = self.next_player(player);
+        if let Some(supplies) = &mut self.supplies {
+
+            if supplies[player as usize] > 0 {
+
+                supplies[player as usize] -= 1;
+
+            } else {
+
+                supplies[opponent as usize] -= 1;
+            }
+        }
+    }
+
+    // the four cells the discs start on
+    pub fn centre(&self) -> [Move; 4] {
+
+        let centre = self.size / 2;
+        [(centre - 1, centre - 1), (centre - 1, centre), (centre, centre - 1), (centre, centre)]
+    }
+
+    // the original Reversi rules have the players fill the centre first, without capturing
+    pub fn is_placement_phase(&self) -> bool {
+        self.is_reversi() && self.centre().iter().any(|&cell| self.cell(cell) == Cell::Empty)
+    }
+
+    pub fn contains(&self, (row, col): Move) -> bool {
+        row < self.size && col < self.size
+    }
 
     pub fn cell(&self, (row, col): Move) -> Cell {
         self.grid[row][col]
     }
+
+    // the cell one step away in the direction, None if it's off the board
+    // on a torus, the step wraps around to the opposite side instead
+    pub fn neighbour(&self, (row, col): Move, (row_direction, col_direction): (i32, i32)) -> Option<Move> {
+
+        let (new_row, new_col) = (row as i32 + row_direction, col as i32 + col_direction);
+        let size = self.size as i32;
+
+        match self.topology {
+            Topology::Flat if new_row < 0 || new_row >= size || new_col < 0 || new_col >= size => None,
+            Topology::Flat => Some((new_row as usize, new_col as usize)),
+            Topology::Torus => Some((new_row.rem_euclid(size) as usize, new_col.rem_euclid(size) as usize)),
+        }
+    }
 }
 
-impl Default for Board {
-    fn default() -> Self {
+// a grid with the columns lettered and the rows numbered, as in the algebraic notation
+//   a b c d
+// 1 - - - -
+// 2 - O X -
+// ...
+impl std::fmt::Display for Board {
 
-        let mut grid = [[Cell::Empty; Board::SIZE]; Board::SIZE];
-        grid[3][3] = Cell::Taken(Player::White);
-        grid[4][4] = Cell::Taken(Player::White);
-        grid[3][4] = Cell::Taken(Player::Black);
-        grid[4][3] = Cell::Taken(Player::Black);
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 
-        Board {
-            grid,
+        let width = self.size.to_string().len();
+        let text: Vec<char> = self.to_text().chars().collect();
+
+        write!(f, "{:width$}", "")?;
+        for col in 0..self.size {
+            write!(f, " {}", (b'a' + col as u8) as char)?;
+        }
+        writeln!(f)?;
+
+        for (row, cells) in text.chunks(self.size).enumerate() {
+
+            write!(f, "{:>width$}", row + 1)?;
+            for cell in cells {
+                write!(f, " {cell}")?;
+            }
+            writeln!(f)?;
         }
+
+        Ok(())
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+
+        Board::new(Board::STANDARD_SIZE)
     }
-}
\ No newline at end of file
+}
@@ -4,9 +4,11 @@ type Move = (usize, usize);
 
 // Common utility types
 // a cache for re-use to avoid unnecesary memory allocations
+// the cells are stored as bytes, which keeps the list small enough to be cheap to set up for every search node
+#[derive(Clone)]
 pub struct CellList {
 
-    pub list: [Move; 64],
+    list: [(u8, u8); Board::MAX_SIZE * Board::MAX_SIZE],
     pub count: usize,
 }
 
@@ -14,20 +16,26 @@ impl Default for CellList {
 
     fn default() -> Self {
 
-        CellList { list: [(Board::SIZE, Board::SIZE); 64], count: 0 }
+        CellList { list: [(Board::MAX_SIZE as u8, Board::MAX_SIZE as u8); Board::MAX_SIZE * Board::MAX_SIZE], count: 0 }
     }
 }
 
 impl CellList {
 
-    pub fn push_back(&mut self, cell: Move) {
+    pub fn push_back(&mut self, (row, col): Move) {
 
-        self.list[self.count] = cell;
+        self.list[self.count] = (row as u8, col as u8);
         self.count += 1;
     }
 
+    pub fn get(&self, index: usize) -> Move {
+
+        let (row, col) = self.list[..self.count][index];
+        (row as usize, col as usize)
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = Move> {
 
-        self.list[..self.count].iter().copied()
+        self.list[..self.count].iter().map(|&(row, col)| (row as usize, col as usize))
     }
 }
\ No newline at end of file
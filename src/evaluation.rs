@@ -0,0 +1,141 @@
+use crate::board::Board;
+use crate::board::Cell;
+use crate::board::Player;
+use crate::board::Ruleset;
+use crate::board::Topology;
+use crate::common::CellList;
+use crate::referee::Referee;
+
+// A linear board evaluation: a weighted sum of features,
+// where every feature is the player's count of something minus the opponent's
+
+pub const FEATURE_COUNT: usize = 6;
+
+pub const FEATURE_NAMES: [&str; FEATURE_COUNT] = [
+    "Disc",
+    "Mobility",
+    "Corner",
+    "X-Square",
+    "C-Square",
+    "Edge",
+];
+
+// disc count only, which is how the agent has always evaluated boards
+pub const DEFAULT_WEIGHTS: [f32; FEATURE_COUNT] = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+
+pub const DISC: usize = 0;
+pub const MOBILITY: usize = 1;
+pub const CORNER: usize = 2;
+pub const X_SQUARE: usize = 3;
+pub const C_SQUARE: usize = 4;
+pub const EDGE: usize = 5;
+
+pub type Features = [f32; FEATURE_COUNT];
+pub type Weights = [f32; FEATURE_COUNT];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Region {
+    Corner,
+    // diagonally adjacent to a corner
+    XSquare,
+    // adjacent to a corner along an edge
+    CSquare,
+    Edge,
+    Inner,
+}
+
+// a torus has no corners or edges, every cell is alike
+pub fn classify(board: &Board, (row, col): (usize, usize)) -> Region {
+
+    if board.topology == Topology::Torus {
+
+        return Region::Inner;
+    }
+
+    let last = board.size() - 1;
+    let distance = |i: usize| i.min(last - i);
+    let (row_distance, col_distance) = (distance(row), distance(col));
+
+    match (row_distance, col_distance) {
+        (0, 0) => Region::Corner,
+        (1, 1) => Region::XSquare,
+        (0, 1) | (1, 0) => Region::CSquare,
+        (0, _) | (_, 0) => Region::Edge,
+        _ => Region::Inner,
+    }
+}
+
+// what a disc is worth to its owner: 1, or -1 under misère rules, where every disc counts against its owner
+pub fn objective(board: &Board) -> f32 {
+
+    match board.ruleset {
+        Ruleset::Standard => 1.0,
+        Ruleset::Misere => -1.0,
+    }
+}
+
+// all features but mobility, which is a lot more expensive to compute
+pub fn count_features(board: &Board, player: Player) -> Features {
+
+    let mut features = [0.0; FEATURE_COUNT];
+    let objective = objective(board);
+
+    for row in 0..board.size() {
+        for col in 0..board.size() {
+
+            let sign = match board.grid[row][col] {
+                Cell::Empty | Cell::Blocked => continue,
+                Cell::Taken(owner) if owner == player => objective,
+                Cell::Taken(_) => -objective,
+            };
+
+            features[DISC] += sign;
+
+            match classify(board, (row, col)) {
+                Region::Corner => features[CORNER] += sign,
+                Region::XSquare => features[X_SQUARE] += sign,
+                Region::CSquare => features[C_SQUARE] += sign,
+                Region::Edge => features[EDGE] += sign,
+                Region::Inner => {},
+            }
+        }
+    }
+
+    features
+}
+
+// the number of valid moves of the player minus that of the next player
+pub fn mobility(referee: &mut Referee, valid_moves: &mut CellList, board: &Board, player: Player) -> f32 {
+
+    referee.find_all_valid_moves(board, player, valid_moves);
+    let player_mobility = valid_moves.count as f32;
+    referee.find_all_valid_moves(board, board.next_player(player), valid_moves);
+    let opponent_mobility = valid_moves.count as f32;
+
+    player_mobility - opponent_mobility
+}
+
+pub fn extract_features(referee: &mut Referee, valid_moves: &mut CellList, board: &Board, player: Player) -> Features {
+
+    let mut features = count_features(board, player);
+    features[MOBILITY] = mobility(referee, valid_moves, board, player);
+
+    features
+}
+
+pub fn evaluate(features: &Features, weights: &Weights) -> f32 {
+
+    features.iter().zip(weights.iter()).map(|(feature, weight)| feature * weight).sum()
+}
+
+// the same as evaluating the extracted features, but skips mobility if it doesn't matter
+// TODO: add end-of-game awareness
+pub fn evaluate_board(referee: &mut Referee, valid_moves: &mut CellList, board: &Board, player: Player, weights: &Weights) -> f32 {
+
+    let mut features = count_features(board, player);
+    if weights[MOBILITY] != 0.0 {
+        features[MOBILITY] = mobility(referee, valid_moves, board, player);
+    }
+
+    evaluate(&features, weights)
+}
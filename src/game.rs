@@ -1,22 +1,45 @@
 use std::thread;
+use std::sync::Arc;
 use std::sync::mpsc;
 use std::time::Duration;
 use std::time::Instant;
-use std::convert::TryFrom;
 
 use eframe::egui;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 
 use crate::common::CellList;
 use crate::board::Player;
 use crate::board::Cell;
+use crate::board::Action;
 use crate::board::Board;
+use crate::board::Ruleset;
+use crate::board::Topology;
+use crate::notation;
+use crate::opening;
+use crate::opening::StartingPosition;
 use crate::agent::Agent;
+use crate::agent::AgentRequest;
+use crate::agent::AgentResponse;
+use crate::agent::AnalysisRequest;
+use crate::agent::AnalysisResult;
+use crate::agent::SearchStatistics;
+use crate::agent::ThinkingReport;
 use crate::agent::MoveResult;
-use crate::agent::AiType;
 use crate::agent::MoveRequest;
+use crate::record::GameRecord;
+use crate::record::Turn;
 use crate::referee::Outcome;
+use crate::referee::Score;
 use crate::referee::Referee;
+use crate::statistics::SearchStatistic;
 use crate::statistics::Statistics;
+use crate::strategy;
+use crate::strategy::Parameter;
+use crate::strategy::RequestKind;
+use crate::strategy::StopFlags;
+use crate::strategy::ParameterValue;
+use crate::strategy::Strategy;
 
 type Move = (usize, usize);
 
@@ -24,8 +47,8 @@ type Move = (usize, usize);
 enum Phase {
 
     Turn(Player),
-    Win(Player),
-    Tie,
+    Win(Player, Score),
+    Tie(Score),
 }
 
 pub struct GameOptions {
@@ -36,6 +59,22 @@ pub struct GameOptions {
     pace_ai: bool,
     pause_at_win: bool,
     should_take_statistics: bool,
+    // replay games with the same seed, instead of drawing a new one for every game
+    fixed_seed: bool,
+    seed: u64,
+    // take effect when the game restarts
+    board_size: usize,
+    // more than two players play Rolit
+    player_count: usize,
+    start: StartingPosition,
+    // blocks the corners of the board
+    cut_corners: bool,
+    ruleset: Ruleset,
+    topology: Topology,
+    // an empty start and limited discs, for two players
+    original_rules: bool,
+    // remembered while another starting position is selected
+    position_text: String,
 }
 
 impl Default for GameOptions {
@@ -50,16 +89,27 @@ impl Default for GameOptions {
             pace_ai: true,
             pause_at_win: true,
             should_take_statistics: true,
+            fixed_seed: false,
+            seed: 0,
+            board_size: Board::STANDARD_SIZE,
+            player_count: 2,
+            start: StartingPosition::Diagonal,
+            cut_corners: false,
+            ruleset: Ruleset::Standard,
+            topology: Topology::Flat,
+            original_rules: false,
+            position_text: String::new(),
         }
     }
 }
 
-#[derive(Clone, Copy)]
 pub struct PlayerOptions {
 
     ai_enabled: bool,
-    ai_type: AiType,
-    ai_recursion_depth: usize,
+    // an index into the strategy registry
+    strategy: usize,
+    // the parameters of every strategy in the registry, so that they are remembered when switching strategies
+    parameters: Vec<Vec<Parameter>>,
 }
 
 impl Default for PlayerOptions {
@@ -69,8 +119,26 @@ impl Default for PlayerOptions {
         PlayerOptions {
 
             ai_enabled: false,
-            ai_type: AiType::Random,
-            ai_recursion_depth: 1,
+            strategy: 0,
+            parameters: strategy::registry().iter().map(|strategy| strategy.parameters()).collect(),
+        }
+    }
+}
+
+pub struct AnalysisOptions {
+
+    recursion_depth: usize,
+    line_count: usize,
+}
+
+impl Default for AnalysisOptions {
+
+    fn default() -> Self {
+
+        AnalysisOptions {
+
+            recursion_depth: 3,
+            line_count: 3,
         }
     }
 }
@@ -79,31 +147,54 @@ pub struct Game {
 
     board: Board,
     current_phase: Phase,
+    // all agent decisions of a game derive from its seed, so that the game can be replayed exactly
+    game_seed: u64,
+    move_count: u64,
+    // every turn so far, for undo and redo
+    record: GameRecord,
     options: GameOptions,
-    player_options: [PlayerOptions; 2],
+    // indexed by player, only the ones taking part in the game are used
+    player_options: [PlayerOptions; Player::COUNT],
+    // used for presenting the strategies, the agent has its own instances
+    strategies: Vec<Box<dyn Strategy>>,
+    analysis_options: AnalysisOptions,
     ai_thread: Option<thread::JoinHandle<()>>,
-    awaiting_ai_move: bool,
-    move_request_sender: Option<mpsc::Sender<MoveRequest>>,
-    move_result_receiver: mpsc::Receiver<MoveResult>,
+    next_request_id: u64,
+    // the ids of requests whose responses are still of interest
+    pending_move_request: Option<u64>,
+    pending_analysis_request: Option<u64>,
+    analysis: Option<AnalysisResult>,
+    thinking: Option<Box<ThinkingReport>>,
+    request_sender: Option<mpsc::Sender<AgentRequest>>,
+    response_receiver: mpsc::Receiver<AgentResponse>,
+    // shared with the agent
+    stop_flags: Arc<StopFlags>,
     referee: Referee,
     valid_moves: CellList,
     flip_cells: CellList,
     scheduled_restart: Instant,
+    // why the chosen starting position couldn't be set up, if it couldn't
+    start_error: Option<String>,
     is_board_untouched: bool,
     can_take_statistics: bool,
     statistics: Statistics,
+    // per player, for the current game
+    search_totals: [SearchStatistic; Player::COUNT],
+    last_search: [Option<SearchStatistics>; Player::COUNT],
 }
 
 impl Default for Game {
 
     fn default() -> Self {
 
-        let (move_request_sender, move_request_receiver) = mpsc::channel::<MoveRequest>();
-        let (move_result_sender, move_result_receiver) = mpsc::channel::<MoveResult>();
+        let (request_sender, request_receiver) = mpsc::channel::<AgentRequest>();
+        let (response_sender, response_receiver) = mpsc::channel::<AgentResponse>();
+        let stop_flags = Arc::new(StopFlags::default());
+        let agent_stop_flags = stop_flags.clone();
         
         let ai_thread = thread::spawn(move || {
             
-            let mut agent = Agent::new(move_request_receiver, move_result_sender);
+            let mut agent = Agent::new(request_receiver, response_sender, agent_stop_flags, rand::random());
             agent.run();
         });
 
@@ -111,19 +202,32 @@ impl Default for Game {
 
             board: Board::default(),
             current_phase: Phase::Turn(Player::Black),
+            game_seed: 0,
+            move_count: 0,
+            record: GameRecord::new(Board::default(), Player::Black, 0),
             options: GameOptions::default(),
-            player_options: [PlayerOptions::default(); 2],
+            player_options: std::array::from_fn(|_| PlayerOptions::default()),
+            strategies: strategy::registry(),
+            analysis_options: AnalysisOptions::default(),
             ai_thread: Some(ai_thread),
-            awaiting_ai_move: false,
-            move_request_sender: Some(move_request_sender),
-            move_result_receiver: move_result_receiver,
+            next_request_id: 1,
+            pending_move_request: None,
+            pending_analysis_request: None,
+            analysis: None,
+            thinking: None,
+            request_sender: Some(request_sender),
+            response_receiver,
+            stop_flags,
             referee: Referee::default(),
             valid_moves: CellList::default(),
             flip_cells: CellList::default(),
             scheduled_restart: Instant::now(),
+            start_error: None,
             is_board_untouched: false,
             can_take_statistics: false,
             statistics: Statistics::default(),
+            search_totals: Default::default(),
+            last_search: Default::default(),
         };
 
         game.reset();
@@ -138,8 +242,9 @@ impl Drop for Game {
 
         println!("Game is being dropped. Cleaning up AI thread...");
 
-        // Drop the sender so AI thread exits
-        self.move_request_sender = None;
+        // Abort whatever the AI is working on and drop the sender so AI thread exits
+        self.stop_flags.stop_all();
+        self.request_sender = None;
 
         // Wait for AI thread to exit
         if let Some(ai_thread) = self.ai_thread.take() {
@@ -155,15 +260,62 @@ impl Game {
     // call this from the UI thread
     fn reset(&mut self) {
 
-        self.board = Board::default();
-        self.current_phase = Phase::Turn(Player::Black);
-        self.referee.find_all_valid_moves(&self.board, Player::Black, &mut self.valid_moves);
+        self.cancel_move_request();
+        self.cancel_analysis_request();
+
+        // short seeds are easier to pass around in bug reports
+        self.game_seed = if self.options.fixed_seed { self.options.seed } else { rand::random::<u32>() as u64 };
+
+        // the seed picks the opening as well, so that replays start the same way
+        let mut rng = StdRng::seed_from_u64(self.game_seed);
+        let (board, player) = if self.options.player_count > 2 {
+
+            // Rolit and the original Reversi have starts of their own
+            self.start_error = None;
+            (Board::new_rolit(self.options.board_size, self.options.player_count), Player::Black)
+
+        } else if self.options.original_rules {
+
+            self.start_error = None;
+            (Board::new_reversi(self.options.board_size), Player::Black)
+
+        } else {
+
+            match opening::set_up(&self.options.start, self.options.board_size, &mut rng) {
+                Ok(position) => {
+                    self.start_error = None;
+                    position
+                }
+                Err(e) => {
+                    self.start_error = Some(e);
+                    (Board::new(self.options.board_size), Player::Black)
+                }
+            }
+        };
+
+        self.board = board;
+        self.board.ruleset = self.options.ruleset;
+        self.board.topology = self.options.topology;
+        if self.options.cut_corners && self.options.topology == Topology::Flat && !matches!(self.options.start, StartingPosition::Text(_)) {
+
+            self.board.cut_corners(self.board.size() / 4);
+        }
+        self.move_count = 0;
+        self.record = GameRecord::new(self.board.clone(), player, self.game_seed);
+        self.search_totals = Default::default();
+        self.last_search = Default::default();
+        // a finished starting position isn't worth any statistics
+        self.can_take_statistics = false;
+        self.begin_turn(player);
         self.is_board_untouched = true;
         self.can_take_statistics = true;
     }
 
     fn ai_setting_changed(&mut self) {
 
+        // the agent may be thinking with the old settings
+        self.cancel_move_request();
+
         // statistics are deemed invalid if the ai settings are changed after the game has started
         if !self.is_board_untouched {
             
@@ -171,80 +323,209 @@ impl Game {
         }
     }
 
+    // every position of the game gets its own seed
+    fn current_seed(&self) -> u64 {
+
+        strategy::position_seed(self.game_seed, self.move_count)
+    }
+
+    fn take_request_id(&mut self) -> u64 {
+
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+        request_id
+    }
+
+    // makes the agent abort the request as soon as possible and return its best move so far
+    fn stop_request(&self, kind: RequestKind, request_id: u64) {
+
+        self.stop_flags.stop(kind, request_id);
+    }
+
+    // stops the pending move request and discards its result
+    fn cancel_move_request(&mut self) {
+
+        if let Some(request_id) = self.pending_move_request.take() {
+
+            self.stop_request(RequestKind::Move, request_id);
+        }
+    }
+
+    fn cancel_analysis_request(&mut self) {
+
+        if let Some(request_id) = self.pending_analysis_request.take() {
+
+            self.stop_request(RequestKind::Analysis, request_id);
+        }
+    }
+
     // call this from the UI thread
-    fn tick_ai(&mut self, player: Player) {
+    // polls for agent responses, non-blocking
+    fn poll_agent(&mut self) {
 
-        // either poll for ai response, non-blocking...
-        if self.awaiting_ai_move {
+        while let Ok(response) = self.response_receiver.try_recv() {
 
-            if let Some(move_result) = self.move_result_receiver.try_recv().ok() {
+            match response {
 
-                let (row, col) = move_result.next_move;
-                if row < Board::SIZE && col < Board::SIZE {
+                AgentResponse::Move(move_result) => {
 
-                    if move_result.board.grid == self.board.grid && move_result.player == player {
-                        
-                        assert!(self.make_move(move_result.next_move, player));
+                    // responses to cancelled requests are stale
+                    if self.pending_move_request == Some(move_result.request_id) {
+
+                        self.pending_move_request = None;
+                        self.handle_move_result(*move_result);
+                    }
+                }
+                AgentResponse::Analysis(analysis_result) => {
+
+                    if self.pending_analysis_request == Some(analysis_result.request_id) {
+
+                        self.pending_analysis_request = None;
+                        self.analysis = Some(*analysis_result);
                     }
+                }
+                AgentResponse::Thinking(thinking_report) => {
 
-                } else {
+                    if self.pending_move_request == Some(thinking_report.request_id) {
 
-                    // unable to come up with a valid move, it seems
-                    self.player_options[player as usize].ai_enabled = false;
+                        self.thinking = Some(thinking_report);
+                    }
                 }
-                self.awaiting_ai_move = false;
             }
+        }
+    }
+
+    fn handle_move_result(&mut self, move_result: MoveResult) {
 
-        } else { //...or ask ai to start thinking about the next move
+        let player = move_result.player;
 
-            if let Some(tx) = &self.move_request_sender {
+        // passes are made when the turn begins, so the agent is only asked when it has a valid move
+        if let Action::Place(next_move) = move_result.action
+            && self.board.contains(next_move) {
 
-                self.awaiting_ai_move = true;
-                let _ = tx.send(MoveRequest {
-                    board: self.board.clone(),
-                    player: player,
-                    pace_response: self.options.pace_ai,
-                    algorithm_choice: self.player_options[player as usize].ai_type,
-                    recursion_depth: self.player_options[player as usize].ai_recursion_depth,
-                });
+            self.search_totals[player as usize].add(&move_result.statistics);
+            if self.options.should_take_statistics {
+
+                self.statistics.add_search_datum(self.player_name(player), &move_result.statistics);
             }
-        }        
+            self.last_search[player as usize] = Some(move_result.statistics);
+
+            assert!(self.make_move(next_move, player));
+
+        } else {
+
+            // unable to come up with a valid move, it seems
+            self.player_options[player as usize].ai_enabled = false;
+        }
     }
 
     // call this from the UI thread
-    fn make_move(&mut self, next_move: Move, player: Player) -> bool {
+    // asks ai to start thinking about the next move, unless it's already doing so
+    fn tick_ai(&mut self, player: Player) {
 
-        // Validate and collect flip cells for ai move
-        if self.referee.find_flip_cells_for_move(&self.board, player, next_move, &mut self.flip_cells) {
-            
-            Referee::apply_move(&mut self.board, player, next_move, &self.flip_cells);
+        if self.pending_move_request.is_none() && self.request_sender.is_some() {
+
+            let request_id = self.take_request_id();
+            self.pending_move_request = Some(request_id);
+            self.thinking = None;
+            let _ = self.request_sender.as_ref().unwrap().send(AgentRequest::Move(MoveRequest {
+                request_id,
+                board: self.board.clone(),
+                player,
+                pace_response: self.options.pace_ai,
+                strategy: self.player_options[player as usize].strategy,
+                parameters: self.active_parameters(player).to_vec(),
+                seed: Some(self.current_seed()),
+            }));
+        }
+    }
 
-            let opponent = player.opponent();
+    // call this from the UI thread
+    fn request_analysis(&mut self, player: Player) {
+
+        if self.request_sender.is_some() {
+
+            let request_id = self.take_request_id();
+            self.pending_analysis_request = Some(request_id);
+            let _ = self.request_sender.as_ref().unwrap().send(AgentRequest::Analysis(AnalysisRequest {
+                request_id,
+                board: self.board.clone(),
+                player,
+                recursion_depth: self.analysis_options.recursion_depth,
+                line_count: self.analysis_options.line_count,
+                seed: Some(self.current_seed()),
+            }));
+        }
+    }
 
-            if self.referee.find_all_valid_moves(&self.board, opponent, &mut self.valid_moves) {
+    fn format_variation(principal_variation: &[Action]) -> String {
 
-                // switch players if the other player has valid moves
-                self.current_phase = Phase::Turn(opponent);
+        let variation: Vec<String> = principal_variation.iter().copied().map(notation::format_action).collect();
+        variation.join(" ")
+    }
 
-            } else if !self.referee.find_all_valid_moves(&self.board, player, &mut self.valid_moves) {
+    // live output of the agent while it's searching for a move
+    fn update_thinking_display(&self, ui: &mut egui::Ui) {
 
-                // no player has any valid moves, game ends
-                let outcome = Referee::check_outcome(&self.board);
-                self.current_phase = match outcome {
+        if let Some(thinking) = &self.thinking
+            && self.pending_move_request == Some(thinking.request_id) {
 
-                    Outcome::Won(player) => Phase::Win(player),
-                    Outcome::Tie => Phase::Tie,
-                };
-                
-                self.take_statistics(outcome);
+            ui.label(format!("Depth {}, {} nodes, {:.0} nodes/s", thinking.depth, thinking.node_count, thinking.nodes_per_second));
+
+            if let Some(best_line) = &thinking.best_line {
+
+                ui.label(format!("Best so far: {} {:+.1}\n{}", notation::format_move(best_line.next_move), best_line.score, Self::format_variation(&best_line.principal_variation)));
+            }
+        }
+    }
+
+    fn update_analysis_controls(&mut self, ui: &mut egui::Ui) {
+
+        ui.label("Analysis");
+        ui.label("Analysis Depth");
+        ui.add(egui::Slider::new(&mut self.analysis_options.recursion_depth, 1..=8).text(""));
+        ui.label("Analysis Lines");
+        ui.add(egui::Slider::new(&mut self.analysis_options.line_count, 1..=10).text(""));
+
+        if let Phase::Turn(player) = self.current_phase
+            && ui.add_enabled(self.pending_analysis_request.is_none(), egui::Button::new("Analyze Position")).clicked() {
+
+            self.request_analysis(player);
+        }
+
+        if let Some(request_id) = self.pending_analysis_request {
+
+            ui.label("Analyzing...");
+            if ui.button("Stop Analysis").clicked() {
 
-                // only used if auto_restart is enabled
-                self.scheduled_restart = Instant::now();
-                if self.options.pause_at_win {
+                self.stop_request(RequestKind::Analysis, request_id);
+            }
+
+        } else if let Some(analysis) = &self.analysis {
+
+            // only show analysis of the position on the board
+            if analysis.board.grid == self.board.grid && matches!(self.current_phase, Phase::Turn(player) if player == analysis.player) {
 
-                    self.scheduled_restart += Duration::from_secs(1);
+                let best_score = analysis.lines.first().map_or(0.0, |line| line.score);
+                for (i, line) in analysis.lines.iter().enumerate() {
+
+                    ui.label(format!("{}. {} {:+.1} ({:+.1})\n{}", i + 1, notation::format_move(line.next_move), line.score, line.score - best_score, Self::format_variation(&line.principal_variation)));
                 }
             }
+        }
+    }
+
+    // call this from the UI thread
+    fn make_move(&mut self, next_move: Move, player: Player) -> bool {
+
+        // Validate and collect flip cells for ai move
+        if self.referee.find_flip_cells_for_move(&self.board, player, next_move, &mut self.flip_cells) {
+            
+            Referee::apply_move(&mut self.board, player, next_move, &self.flip_cells);
+            self.move_count += 1;
+            self.record.push(Turn { player, action: Action::Place(next_move) });
+
+            self.begin_turn(self.board.next_player(player));
             
             if self.is_board_untouched {
                 
@@ -261,38 +542,146 @@ impl Game {
         }
     }
 
+    fn is_human(&self, player: Player) -> bool {
+
+        !self.player_options[player as usize].ai_enabled
+    }
+
+    fn can_undo(&self) -> bool {
+
+        self.record.can_undo(|player| self.is_human(player))
+    }
+
+    // call this from the UI thread
+    fn undo(&mut self) {
+
+        let player_options = &self.player_options;
+        if self.record.undo(|player| !player_options[player as usize].ai_enabled) {
+
+            self.rebuild_from_record();
+        }
+    }
+
+    // call this from the UI thread
+    fn redo(&mut self) {
+
+        let player_options = &self.player_options;
+        if self.record.redo(|player| !player_options[player as usize].ai_enabled) {
+
+            self.rebuild_from_record();
+        }
+    }
+
+    // sets the board and phase to the current position of the record
+    fn rebuild_from_record(&mut self) {
+
+        // the agent may be thinking about a position that is gone
+        self.cancel_move_request();
+        self.cancel_analysis_request();
+
+        let (board, player) = self.record.replay(&mut self.referee);
+        self.board = board;
+        self.move_count = self.record.move_count() as u64;
+
+        // a game with moves taken back isn't worth any statistics
+        self.can_take_statistics = false;
+        self.is_board_untouched = false;
+        self.begin_turn(player);
+    }
+
+    // the player moves next, unless they have to pass
+    fn begin_turn(&mut self, player: Player) {
+
+        let mut next_player = player;
+        for _ in 0..self.board.player_count() {
+
+            if self.referee.find_all_valid_moves(&self.board, next_player, &mut self.valid_moves) {
+
+                // the players before have to pass
+                let mut passing_player = player;
+                while passing_player != next_player {
+
+                    self.record.push(Turn { player: passing_player, action: Action::Pass });
+                    passing_player = self.board.next_player(passing_player);
+                }
+
+                self.current_phase = Phase::Turn(next_player);
+                return;
+            }
+
+            next_player = self.board.next_player(next_player);
+        }
+
+        // no player has any valid moves, game ends
+        let outcome = Referee::check_outcome(&self.board);
+        self.current_phase = match outcome {
+
+            Outcome::Won(player, score) => Phase::Win(player, score),
+            Outcome::Tie(score) => Phase::Tie(score),
+        };
+        
+        self.take_statistics(outcome);
+
+        // only used if auto_restart is enabled
+        self.scheduled_restart = Instant::now();
+        if self.options.pause_at_win {
+
+            self.scheduled_restart += Duration::from_secs(1);
+        }
+    }
+
+    fn active_parameters(&self, player: Player) -> &[Parameter] {
+
+        let player_options = &self.player_options[player as usize];
+        &player_options.parameters[player_options.strategy]
+    }
+
+    // tells players apart in the statistics
+    fn player_name(&self, player: Player) -> String {
+
+        let player_options = &self.player_options[player as usize];
+        if player_options.ai_enabled {
+            self.strategies[player_options.strategy].label(self.active_parameters(player))
+        } else {
+            "Human".to_string()
+        }
+    }
+
     fn take_statistics(&mut self, outcome: Outcome) {
         
         if self.can_take_statistics {
 
-            let mut names: [String; 2] = [String::new(), String::new()];
-            for i in 0..2 {
+            let mut names: Vec<(String, Player)> = self.board.players().map(|player| (self.player_name(player), player)).collect();
 
-                let player_name = format!("{}", if self.player_options[i].ai_enabled {
-                    match self.player_options[i].ai_type {
-                        AiType::Random => format!("Random"),
-                        AiType::Minimax => format!("Minimax lvl {}", self.player_options[i].ai_recursion_depth)
-                    }
-                } else {
-                    format!("Human")
-                });
+            // sort so that another player color doesn't render another entry
+            names.sort_by(|a, b| a.0.cmp(&b.0));
+            let first_player = names[0].1;
+            
+            let mut matchup = names.iter().map(|(name, _player)| name.as_str()).collect::<Vec<_>>().join(" vs ");
+            // games on other board sizes or by other rules are kept apart
+            let mut variants = Vec::new();
+            if self.board.size() != Board::STANDARD_SIZE {
 
-                names[i] = player_name;
+                variants.push(format!("{0}x{0}", self.board.size()));
             }
+            if self.board.ruleset != Ruleset::Standard {
 
-            // sort so that another player color doesn't render another entry
-            let first_player =
-                if names[0] < names[1] {
-                
-                    Player::Black
+                variants.push(self.board.ruleset.name().to_string());
+            }
+            if self.board.topology != Topology::Flat {
+
+                variants.push(self.board.topology.name().to_string());
+            }
+            if self.board.is_reversi() {
 
-                } else {
+                variants.push("Original".to_string());
+            }
+            if !variants.is_empty() {
 
-                    Player::White
+                matchup = format!("{matchup} ({})", variants.join(", "));
+            }
 
-                };
-            
-            self.statistics.add_datum(format!("{} vs {}", names[first_player as usize], names[(first_player as usize + 1) % 2]), first_player, &outcome);
+            self.statistics.add_datum(matchup, first_player, &outcome);
             
             self.can_take_statistics = false;
         }
@@ -301,55 +690,160 @@ impl Game {
 
     fn update_player_options_controls(&mut self, ui: &mut egui::Ui, player: Player) {
         
-        // Define the maximum depth for the minimax algorithm
-        let max_depth = 10;
-        
         ui.label(format!("{:?} Player Options", player));
         if ui.checkbox(&mut self.player_options[player as usize].ai_enabled, "Enable AI").changed() {
 
             self.ai_setting_changed();
         }
         ui.label("AI Type");
-        self.player_options[player as usize].ai_type = self.update_ai_type_radio_buttons(ui, self.player_options[player as usize].ai_type, player);
-        // a slider for the minimax algorithm recursion depth
-        ui.label("AI Recursion Depth");
-        if ui.add(egui::Slider::new(&mut self.player_options[player as usize].ai_recursion_depth, 1..=max_depth).text("")).changed() {
+        self.update_ai_type_radio_buttons(ui, player);
+        self.update_parameter_controls(ui, player);
+        self.update_search_display(ui, player);
+    }  
+
+    // the starting position of the next games
+    fn update_start_controls(&mut self, ui: &mut egui::Ui) {
+
+        ui.label("Start");
+
+        // the position text has a shape of its own, and a torus has no corners to cut
+        let can_cut_corners = self.options.topology == Topology::Flat && !matches!(self.options.start, StartingPosition::Text(_));
+        if ui.add_enabled(can_cut_corners, egui::Checkbox::new(&mut self.options.cut_corners, "Octagon Board")).changed() {
+
+            self.reset();
+        }
+
+        if self.options.player_count > 2 {
+
+            ui.label("One disc of every color, for Rolit");
+            return;
+        }
+
+        if self.options.original_rules {
+
+            ui.label("An empty board, for the original Reversi");
+            return;
+        }
+
+        let position_text = StartingPosition::Text(self.options.position_text.clone());
+        let starts = [
+            (StartingPosition::Diagonal, "Diagonal"),
+            (StartingPosition::Parallel, "Parallel"),
+            (StartingPosition::Xot, "Random XOT Opening"),
+            (position_text, "Position Text"),
+        ];
+
+        let mut changed = false;
+        for (start, name) in starts {
 
-            if self.player_options[player as usize].ai_enabled && self.player_options[player as usize].ai_type == AiType::Minimax {
-                self.ai_setting_changed();
+            if ui.radio(std::mem::discriminant(&self.options.start) == std::mem::discriminant(&start), name).clicked() {
+
+                self.options.start = start;
+                changed = true;
             }
         }
 
-    }  
+        if let StartingPosition::Text(text) = &mut self.options.start {
 
-    // closure that handles the dynamic depth options
-    fn update_ai_type_radio_buttons(&mut self, ui: &mut egui::Ui, ai_type: AiType, player: Player) -> AiType {
+            // X for black, O for white, - for empty, # for blocked, row by row, optionally followed by X or O for the side to move
+            let response = ui.text_edit_multiline(&mut self.options.position_text);
+            if response.lost_focus() {
 
-        let mut options = Vec::new();
-        options.push("Random".to_string());
-        options.push("Minimax".to_string());
-    
-        let mut result = ai_type;
-    
-        // Display dynamic depth options in a loop
-        for (i, option) in options.iter().enumerate() {
+                *text = self.options.position_text.clone();
+                changed = true;
+            }
+        }
 
-            if ui.radio(ai_type as usize == i, option).clicked() {
+        if let Some(start_error) = &self.start_error {
 
-                result = match AiType::try_from(i) {
-                    Ok(agent_type) => agent_type,
-                    Err(e) => {
-                        panic!("AI type conversion failed: {e}")
-                    }
-                };
+            ui.label(format!("{start_error}, using the diagonal start"));
+        }
+
+        if changed {
+
+            self.reset();
+        }
+    }
+
+    // the moves so far, as a transcript that can be copied, and so can the position, for bug reports
+    fn update_history_display(&self, ui: &mut egui::Ui) {
+
+        ui.label("History");
+
+        let transcript = notation::format_transcript(self.record.turns());
+        ui.label(format!("{} moves", self.record.move_count()));
+        ui.add(egui::Label::new(egui::RichText::new(&transcript).monospace()).wrap());
+
+        ui.horizontal(|ui| {
+
+            if ui.add_enabled(!transcript.is_empty(), egui::Button::new("Copy Transcript")).clicked() {
+
+                ui.ctx().copy_text(transcript);
+            }
+            if let Phase::Turn(player) = self.current_phase
+                && ui.button("Copy Position").clicked() {
+
+                ui.ctx().copy_text(self.board.to_position_text(player));
+            }
+        });
+    }
+
+    // how the player's agent has been doing in the current game
+    fn update_search_display(&self, ui: &mut egui::Ui, player: Player) {
+
+        if let Some(last_search) = &self.last_search[player as usize] {
+
+            ui.label(format!("Search: {}", self.search_totals[player as usize]));
+
+            let mut last_move = format!("Last move: {} nodes, {:.0} nodes/s", last_search.node_count, last_search.nodes_per_second());
+            if let Some(score) = last_search.score {
+
+                last_move += &format!(", depth {}, score {score:+.1}\n{}", last_search.depth, Self::format_variation(&last_search.principal_variation));
+            }
+            ui.label(last_move);
+        }
+    }
+
+    // one radio button for every strategy in the registry
+    fn update_ai_type_radio_buttons(&mut self, ui: &mut egui::Ui, player: Player) {
+
+        for i in 0..self.strategies.len() {
+
+            if ui.radio(self.player_options[player as usize].strategy == i, self.strategies[i].name()).clicked() {
+
+                self.player_options[player as usize].strategy = i;
 
                 if self.player_options[player as usize].ai_enabled {
                     self.ai_setting_changed();
                 }
             }
         }
-    
-        result
+    }
+
+    // controls for the parameters of the chosen strategy
+    fn update_parameter_controls(&mut self, ui: &mut egui::Ui, player: Player) {
+
+        let player_options = &mut self.player_options[player as usize];
+        let mut changed = false;
+
+        for parameter in player_options.parameters[player_options.strategy].iter_mut() {
+
+            ui.label(parameter.name);
+            match &mut parameter.value {
+                ParameterValue::Integer { value, min, max } => {
+                    changed |= ui.add(egui::Slider::new(value, *min..=*max).text("")).changed();
+                }
+                ParameterValue::Text(value) => {
+                    // only count finished edits, rather than every keystroke
+                    changed |= ui.text_edit_singleline(value).lost_focus();
+                }
+            }
+        }
+
+        if changed && player_options.ai_enabled {
+
+            self.ai_setting_changed();
+        }
     }
 }
 
@@ -357,18 +851,32 @@ impl eframe::App for Game {
     
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
 
+        // Ctrl+Shift+Z has to be checked before Ctrl+Z, which would match it too
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z) || i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y)) {
+
+            self.redo();
+
+        } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z)) {
+
+            self.undo();
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
 
+            self.poll_agent();
+
             // UI drawing
 
             let rect = ui.available_rect_before_wrap();
-            let square_size = rect.width().min(rect.height()) / Board::SIZE as f32;
+            let square_size = rect.width().min(rect.height()) / self.board.size() as f32;
             let line_width = square_size * 0.01;
 
             let to_color = |player| match player {
                 
                 Player::Black => egui::Color32::BLACK,
                 Player::White => egui::Color32::WHITE,
+                Player::Red => egui::Color32::from_rgb(200, 30, 30),
+                Player::Blue => egui::Color32::from_rgb(30, 60, 200),
             };
 
             let get_square_rect = |row, col| {
@@ -378,12 +886,13 @@ impl eframe::App for Game {
             };
 
             // draw the current board state
-            for row in 0..Board::SIZE {
-                for col in 0..Board::SIZE {
+            for row in 0..self.board.size() {
+                for col in 0..self.board.size() {
 
                     let square_rect = get_square_rect(row, col);
 
-                    ui.painter().rect_filled(square_rect, 0.0, egui::Color32::DARK_GREEN);
+                    let square_color = if self.board.grid[row][col] == Cell::Blocked { egui::Color32::DARK_GRAY } else { egui::Color32::DARK_GREEN };
+                    ui.painter().rect_filled(square_rect, 0.0, square_color);
 
                     let stroke = egui::Stroke { width: line_width, color: egui::Color32::BLACK };
                     ui.painter().rect_stroke(square_rect, 0.0, stroke, egui::StrokeKind::Inside);
@@ -417,8 +926,8 @@ impl eframe::App for Game {
                     }
 
                     // Mouse handling
-                    let mut row = Board::SIZE;
-                    let mut col = Board::SIZE;
+                    let mut row = Board::MAX_SIZE;
+                    let mut col = Board::MAX_SIZE;
 
                     let mut is_valid_move = false;
                     
@@ -428,7 +937,7 @@ impl eframe::App for Game {
                         row = ((mouse_pos.y - rect.top()) / square_size) as usize;
                         col = ((mouse_pos.x - rect.left()) / square_size) as usize;
                     
-                        if row < Board::SIZE && col < Board::SIZE {
+                        if self.board.contains((row, col)) {
 
                             // this could be optimized by only doing it when the mouse changes cells
                             is_valid_move = self.referee.find_flip_cells_for_move(&self.board, player, (row, col), &mut self.flip_cells);
@@ -452,15 +961,12 @@ impl eframe::App for Game {
                     }
         
                     // handle mouse clicks to make moves
-                    if ui.input(|i| i.pointer.any_down()) {
-                    
-                        if row < Board::SIZE && col < Board::SIZE && is_valid_move {
+                    if ui.input(|i| i.pointer.any_down()) && self.board.contains((row, col)) && is_valid_move {
                         
-                            assert!(self.make_move((row, col), player));
-                        }
+                        assert!(self.make_move((row, col), player));
                     }
                 }
-                Phase::Win(_) | Phase::Tie => {
+                Phase::Win(..) | Phase::Tie(_) => {
                     
                     if self.options.auto_restart && Instant::now() >= self.scheduled_restart {
 
@@ -482,7 +988,7 @@ impl eframe::App for Game {
 
                 Phase::Turn(player) => {
                     
-                    if self.awaiting_ai_move && self.player_options[player as usize].ai_enabled {
+                    if self.pending_move_request.is_some() && self.player_options[player as usize].ai_enabled {
 
                         format!("{:?} is thinking...", player)
 
@@ -491,27 +997,38 @@ impl eframe::App for Game {
                         format!("{:?}'s turn", player)
                     }
                 }
-                Phase::Win(player) => {
+                Phase::Win(player, score) => {
                     
-                    format!("{:?} won", player)
+                    format!("{:?} won {score}", player)
                 }
-                Phase::Tie => {
+                Phase::Tie(score) => {
 
-                    "Tie".to_string()
+                    format!("Tie {score}")
                 },
             };
 
-            ui.label(message);
+            ui.label(format!("{} rules: {message}", self.board.ruleset.name()));
+            if let Some(last_turn) = self.record.turns().last() {
 
-            ui.separator();
+                ui.label(notation::describe_turn(last_turn));
+            }
 
-            self.update_player_options_controls(ui, Player::Black);
+            if self.board.is_reversi() {
 
-            ui.separator();
-            
-            self.update_player_options_controls(ui, Player::White);
+                let supplies: Vec<String> = self.board.players().map(|player| format!("{player:?} {}", self.board.supply(player).unwrap_or_default())).collect();
+                ui.label(format!("Discs left: {}", supplies.join(", ")));
+            }
+
+            self.update_thinking_display(ui);
 
             ui.separator();
+
+            for player in self.board.players() {
+
+                self.update_player_options_controls(ui, player);
+
+                ui.separator();
+            }
             
             ui.label("Control");
             // Continue with other checkboxes and buttons
@@ -519,10 +1036,78 @@ impl eframe::App for Game {
 
                 self.reset();
             }
+            // against the AI, the moves of both sides are taken back
+            ui.horizontal(|ui| {
+
+                if ui.add_enabled(self.can_undo(), egui::Button::new("Undo")).on_hover_text("Ctrl+Z").clicked() {
+
+                    self.undo();
+                }
+                if ui.add_enabled(self.record.can_redo(), egui::Button::new("Redo")).on_hover_text("Ctrl+Y").clicked() {
+
+                    self.redo();
+                }
+            });
+            ui.label("Board Size");
+            if ui.add(egui::Slider::new(&mut self.options.board_size, Board::MIN_SIZE..=Board::MAX_SIZE).step_by(2.0).text("")).changed() {
+
+                self.reset();
+            }
+            ui.label("Players");
+            if ui.add(egui::Slider::new(&mut self.options.player_count, 2..=Player::COUNT).text("")).changed() {
+
+                self.reset();
+            }
+            self.update_start_controls(ui);
+            ui.label("Rules");
+            for ruleset in [Ruleset::Standard, Ruleset::Misere] {
+
+                if ui.radio_value(&mut self.options.ruleset, ruleset, ruleset.name()).clicked() {
+
+                    self.reset();
+                }
+            }
+            ui.label("Topology");
+            for topology in [Topology::Flat, Topology::Torus] {
+
+                if ui.radio_value(&mut self.options.topology, topology, topology.name()).clicked() {
+
+                    self.reset();
+                }
+            }
+            // the players fill the centre themselves, and a player out of discs is handed them by the opponent
+            if ui.add_enabled(self.options.player_count == 2, egui::Checkbox::new(&mut self.options.original_rules, "Original Reversi")).changed() {
+
+                self.reset();
+            }
+            // make the agent play the best move it has found so far
+            if let Some(request_id) = self.pending_move_request
+                && ui.add_enabled(!self.stop_flags.is_stopped(RequestKind::Move, request_id), egui::Button::new("Stop Thinking / Move Now")).clicked() {
+
+                self.stop_request(RequestKind::Move, request_id);
+            }
             ui.checkbox(&mut self.options.auto_restart, "Auto Restart");
 
             ui.separator();
 
+            ui.label("Seed");
+            ui.label(format!("Game seed: {}", self.game_seed));
+            ui.checkbox(&mut self.options.fixed_seed, "Fixed Seed");
+            ui.add_enabled(self.options.fixed_seed, egui::DragValue::new(&mut self.options.seed));
+            if ui.button("Replay Game").clicked() {
+
+                // the same seed and settings play out the same game
+                self.options.seed = self.game_seed;
+                self.options.fixed_seed = true;
+                self.reset();
+            }
+
+            ui.separator();
+
+            self.update_history_display(ui);
+
+            ui.separator();
+
             ui.label("Flow");
             ui.checkbox(&mut self.options.pace_ai, "Pace AI");
             ui.checkbox(&mut self.options.pause_at_win, "Pause at Win");
@@ -535,6 +1120,10 @@ impl eframe::App for Game {
 
             ui.separator();
 
+            self.update_analysis_controls(ui);
+
+            ui.separator();
+
             ui.label("Statistics");
             ui.checkbox(&mut self.options.should_take_statistics, "Take Statistics");
             let modus = match (self.can_take_statistics, self.options.should_take_statistics) {
@@ -546,12 +1135,20 @@ impl eframe::App for Game {
 
             ui.separator();
 
-            ui.label("Won%, Tied%, Lost%, (Total):");
+            ui.label("Won%, Tied%, Lost%, Margin, (Total):");
             for (name, statistic) in self.statistics.data.iter() {
 
                 ui.label(format!("{name}:\n{statistic}"));
             }
 
+            ui.separator();
+
+            ui.label("Search Performance:");
+            for (name, search_statistic) in self.statistics.search_data.iter() {
+
+                ui.label(format!("{name}:\n{search_statistic}"));
+            }
+
         });
     }
 }
\ No newline at end of file
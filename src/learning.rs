@@ -0,0 +1,293 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::board::Action;
+use crate::board::Board;
+use crate::board::Player;
+use crate::common::CellList;
+use crate::evaluation;
+use crate::evaluation::Features;
+use crate::evaluation::Weights;
+use crate::minimax;
+use crate::referee::Referee;
+use crate::strategy::Parameter;
+use crate::strategy::SearchContext;
+use crate::strategy::Strategy;
+
+type Move = (usize, usize);
+
+// Learns the weights of the linear evaluation by playing against itself, using TD(lambda)
+// the evaluation is trained to predict the final disc difference from black's point of view,
+// which, since every feature is antisymmetric, is the inverse of white's
+// the prediction is squashed into the range of possible outcomes, which keeps the learning stable,
+// and since squashing preserves the order of evaluations, the weights still work for a linear evaluation
+
+// the largest possible disc difference, training always plays on the standard board
+const OUTCOME_SCALE: f32 = (Board::STANDARD_SIZE * Board::STANDARD_SIZE) as f32;
+
+pub struct TrainingConfig {
+
+    pub learning_rate: f32,
+    // how much of the credit for a prediction error goes to earlier positions
+    pub lambda: f32,
+    // the probability of a random move instead of the best one, so that new positions are explored
+    pub exploration_rate: f64,
+}
+
+impl Default for TrainingConfig {
+
+    fn default() -> Self {
+
+        TrainingConfig {
+            learning_rate: 0.03,
+            lambda: 0.7,
+            exploration_rate: 0.1,
+        }
+    }
+}
+
+// the state of training, which is what gets saved to and loaded from disk
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+
+    pub weights: Weights,
+    pub game_count: u64,
+}
+
+impl Default for Checkpoint {
+
+    fn default() -> Self {
+
+        Checkpoint { weights: evaluation::DEFAULT_WEIGHTS, game_count: 0 }
+    }
+}
+
+impl Checkpoint {
+
+    // a line with the game count, followed by a line per feature with its name and weight
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+
+        let mut text = format!("games {}\n", self.game_count);
+        for (name, weight) in evaluation::FEATURE_NAMES.iter().zip(self.weights) {
+            text += &format!("{name} {weight}\n");
+        }
+
+        // write to the side first, so that an interrupted save doesn't ruin the previous checkpoint
+        let temporary_path = path.with_extension("tmp");
+        fs::write(&temporary_path, text)?;
+        fs::rename(&temporary_path, path)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let text = fs::read_to_string(path)?;
+        let mut checkpoint = Checkpoint::default();
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+
+            let (name, value) = line.trim().rsplit_once(' ').ok_or_else(|| invalid(format!("malformed line: {line}")))?;
+
+            if name == "games" {
+
+                checkpoint.game_count = value.parse().map_err(|_| invalid(format!("invalid game count: {value}")))?;
+
+            } else {
+
+                let feature = evaluation::FEATURE_NAMES.iter().position(|feature_name| *feature_name == name)
+                    .ok_or_else(|| invalid(format!("unknown feature: {name}")))?;
+                checkpoint.weights[feature] = value.parse().map_err(|_| invalid(format!("invalid weight: {value}")))?;
+            }
+        }
+
+        Ok(checkpoint)
+    }
+}
+
+pub struct Trainer {
+
+    pub checkpoint: Checkpoint,
+    config: TrainingConfig,
+    rng: StdRng,
+    referee: Referee,
+    valid_moves: CellList,
+    flip_cells: CellList,
+}
+
+impl Trainer {
+
+    pub fn new(checkpoint: Checkpoint, config: TrainingConfig, seed: u64) -> Self {
+
+        Trainer {
+            checkpoint,
+            config,
+            rng: StdRng::seed_from_u64(seed),
+            referee: Referee::default(),
+            valid_moves: CellList::default(),
+            flip_cells: CellList::default(),
+        }
+    }
+
+    fn features(&mut self, board: &Board) -> Features {
+
+        evaluation::extract_features(&mut self.referee, &mut self.valid_moves, board, Player::Black)
+    }
+
+    // the predicted final disc difference, from black's point of view, scaled to [-1, 1]
+    fn predict(&self, features: &Features) -> f32 {
+
+        (evaluation::evaluate(features, &self.checkpoint.weights) / OUTCOME_SCALE).tanh()
+    }
+
+    // the mover picks the move leading to the best prediction, except when exploring
+    fn choose_move(&mut self, board: &Board, player: Player) -> Move {
+
+        self.referee.find_all_valid_moves(board, player, &mut self.valid_moves);
+        let valid_moves = self.valid_moves.clone();
+
+        if self.rng.random_bool(self.config.exploration_rate) {
+
+            return valid_moves.get(self.rng.random_range(..valid_moves.count));
+        }
+
+        let sign = if player == Player::Black { 1.0 } else { -1.0 };
+        let mut optimal_move = valid_moves.get(0);
+        let mut optimal_score = f32::NEG_INFINITY;
+
+        for next_move in valid_moves.iter() {
+
+            let mut new_board = board.clone();
+            self.referee.find_flip_cells_for_move(&new_board, player, next_move, &mut self.flip_cells);
+            Referee::apply_move(&mut new_board, player, next_move, &self.flip_cells);
+
+            let features = self.features(&new_board);
+            let score = sign * self.predict(&features);
+            if score > optimal_score {
+
+                optimal_score = score;
+                optimal_move = next_move;
+            }
+        }
+
+        optimal_move
+    }
+
+    // plays one game against itself, updating the weights after every move
+    // returns the final disc difference, from black's point of view
+    pub fn train_game(&mut self) -> i32 {
+
+        self.train_game_from(Board::default(), Player::Black)
+    }
+
+    // the same, but from the given position of a standard board
+    pub fn train_game_from(&mut self, start: Board, first_player: Player) -> i32 {
+
+        let mut board = start;
+        let mut player = first_player;
+
+        let mut features = self.features(&board);
+        let mut eligibility_traces = [0.0; evaluation::FEATURE_COUNT];
+
+        loop {
+
+            if !self.referee.find_all_valid_moves(&board, player, &mut self.valid_moves) {
+
+                player = player.opponent();
+                if !self.referee.find_all_valid_moves(&board, player, &mut self.valid_moves) {
+                    break;
+                }
+            }
+
+            let next_move = self.choose_move(&board, player);
+            self.referee.find_flip_cells_for_move(&board, player, next_move, &mut self.flip_cells);
+            Referee::apply_move(&mut board, player, next_move, &self.flip_cells);
+            player = player.opponent();
+
+            let next_features = self.features(&board);
+            let error = self.predict(&next_features) - self.predict(&features);
+            self.update(&features, &mut eligibility_traces, error);
+
+            features = next_features;
+        }
+
+        // the last prediction is corrected by the actual outcome
+        let (black_count, white_count) = Referee::count_disks(&board, Player::Black);
+        let disc_difference = black_count - white_count;
+        let error = disc_difference as f32 / OUTCOME_SCALE - self.predict(&features);
+        self.update(&features, &mut eligibility_traces, error);
+
+        self.checkpoint.game_count += 1;
+
+        disc_difference
+    }
+
+    // error is the difference between the next prediction and the prediction of the given features
+    fn update(&mut self, features: &Features, eligibility_traces: &mut [f32; evaluation::FEATURE_COUNT], error: f32) {
+
+        // the gradient of the prediction with respect to the weights,
+        // leaving out the constant 1 / OUTCOME_SCALE, which would only scale the learning rate
+        let prediction = self.predict(features);
+        let slope = 1.0 - prediction * prediction;
+
+        for i in 0..evaluation::FEATURE_COUNT {
+
+            eligibility_traces[i] = self.config.lambda * eligibility_traces[i] + slope * features[i];
+            self.checkpoint.weights[i] += self.config.learning_rate * error * eligibility_traces[i];
+        }
+    }
+}
+
+// searches with the weights of a training checkpoint
+#[derive(Default)]
+pub struct LearnedStrategy {
+
+    // the path and the checkpoint loaded from it
+    loaded: Option<(PathBuf, Checkpoint)>,
+}
+
+impl Strategy for LearnedStrategy {
+
+    fn name(&self) -> &'static str {
+
+        "Learned"
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+
+        vec![
+            Parameter::integer("Depth", 1, 1, 10),
+            Parameter::text("Weights File", "weights.txt"),
+        ]
+    }
+
+    fn label(&self, parameters: &[Parameter]) -> String {
+
+        format!("Learned {} lvl {}", parameters[1].as_text(), parameters[0].as_integer())
+    }
+
+    // falls back on the default weights if the checkpoint can't be loaded
+    fn choose_move(&mut self, search: &mut SearchContext, board: &Board, player: Player, parameters: &[Parameter]) -> Action {
+
+        let recursion_depth = parameters[0].as_integer() as usize;
+        let path = PathBuf::from(parameters[1].as_text());
+
+        if self.loaded.as_ref().is_none_or(|(loaded_path, _)| *loaded_path != path) {
+
+            let checkpoint = Checkpoint::load(&path).unwrap_or_else(|e| {
+                eprintln!("Unable to load weights from {}: {e}", path.display());
+                Checkpoint::default()
+            });
+            self.loaded = Some((path, checkpoint));
+        }
+
+        let weights = self.loaded.as_ref().unwrap().1.weights;
+        minimax::find_best_move(search, board, player, recursion_depth, &weights)
+    }
+}
@@ -0,0 +1,20 @@
+pub mod board;
+pub mod bitboard;
+pub mod game;
+pub mod agent;
+pub mod strategy;
+pub mod baseline;
+pub mod minimax;
+pub mod perft;
+pub mod evaluation;
+pub mod selfplay;
+pub mod tuning;
+pub mod learning;
+pub mod nboard;
+pub mod notation;
+pub mod opening;
+pub mod record;
+pub mod common;
+pub mod referee;
+pub mod statistics;
+pub mod symmetry;
@@ -1,12 +1,5 @@
-mod board;
-mod game;
-mod agent;
-mod common;
-mod referee;
-mod statistics;
-
 use eframe::egui;
-use game::Game;
+use othello::game::Game;
 
 fn main() {
     let app = Game::default();
@@ -19,4 +12,3 @@ fn main() {
         Box::new(|_cc| Ok(Box::new(app))),
     );
 }
-
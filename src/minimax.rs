@@ -0,0 +1,383 @@
+use rand::Rng;
+
+use crate::board::Action;
+use crate::board::Player;
+use crate::board::Board;
+use crate::agent::AnalysisLine;
+use crate::evaluation;
+use crate::evaluation::Weights;
+use crate::strategy::Parameter;
+use crate::strategy::SearchContext;
+use crate::strategy::Strategy;
+
+type Move = (usize, usize);
+
+// uses an algorithm that will try to find a move that maximizes oneself and minimizes the opponent
+// with more than two players, it's max-n instead, where every player maximizes their own score
+pub struct MinimaxStrategy;
+
+// the score of every player, indexed by player
+type Scores = [f32; Player::COUNT];
+
+const WEIGHT_LIMIT: i32 = 100;
+
+const WEIGHT_NAMES: [&str; evaluation::FEATURE_COUNT] = [
+    "Disc Weight",
+    "Mobility Weight",
+    "Corner Weight",
+    "X-Square Weight",
+    "C-Square Weight",
+    "Edge Weight",
+];
+
+fn weights_from_parameters(parameters: &[Parameter]) -> Weights {
+
+    std::array::from_fn(|i| parameters[1 + i].as_integer() as f32)
+}
+
+impl Strategy for MinimaxStrategy {
+
+    fn name(&self) -> &'static str {
+
+        "Minimax"
+    }
+
+    // the depth, followed by a weight for every evaluation feature
+    fn parameters(&self) -> Vec<Parameter> {
+
+        // setting the depth to 8 or higher may cause the agent to take a long time to make a move
+        let mut parameters = vec![Parameter::integer("Depth", 1, 1, 10)];
+        for (name, weight) in WEIGHT_NAMES.iter().zip(evaluation::DEFAULT_WEIGHTS) {
+            parameters.push(Parameter::integer(name, weight as i32, -WEIGHT_LIMIT, WEIGHT_LIMIT));
+        }
+        parameters
+    }
+
+    fn label(&self, parameters: &[Parameter]) -> String {
+
+        let weights = weights_from_parameters(parameters);
+        if weights == evaluation::DEFAULT_WEIGHTS {
+
+            format!("Minimax lvl {}", parameters[0].as_integer())
+
+        } else {
+
+            format!("Minimax lvl {} {:?}", parameters[0].as_integer(), weights.map(|weight| weight as i32))
+        }
+    }
+
+    fn choose_move(&mut self, search: &mut SearchContext, board: &Board, player: Player, parameters: &[Parameter]) -> Action {
+
+        let recursion_depth = parameters[0].as_integer() as usize;
+        let weights = weights_from_parameters(parameters);
+
+        find_best_move(search, board, player, recursion_depth, &weights)
+    }
+}
+
+// the search is deepened iteratively, so that there is a best move to report or fall back on at all times
+// passes if there is no valid move
+pub fn find_best_move(search: &mut SearchContext, board: &Board, player: Player, recursion_depth: usize, weights: &Weights) -> Action {
+
+    let mut optimal_move = None;
+
+    for depth in 1..=recursion_depth {
+
+        search.report_depth(depth);
+
+        let mut principal_variation = Vec::new();
+        let (depth_optimal_move, depth_optimal_score) = if board.is_rolit() {
+
+            let (depth_optimal_move, depth_optimal_scores) = find_best_move_max_n(search, board, player, depth, weights, &mut principal_variation);
+            (depth_optimal_move, depth_optimal_scores[player as usize])
+
+        } else {
+
+            find_best_move_recursive(search, board, player, depth, weights, &mut principal_variation)
+        };
+
+        // an interrupted search is only better than nothing
+        if search.is_stopped() && optimal_move.is_some() {
+            break;
+        }
+
+        optimal_move = depth_optimal_move;
+        let Some(next_move) = optimal_move else {
+            break;
+        };
+
+        search.report_best_line(AnalysisLine { next_move, score: depth_optimal_score, principal_variation });
+
+        if search.is_stopped() {
+            break;
+        }
+    }
+
+    optimal_move.map_or(Action::Pass, Action::Place)
+}
+
+// scores every valid move separately, so that the runner-ups can be compared with the best move
+// returns the lines sorted from best to worst
+pub fn analyze(search: &mut SearchContext, board: &Board, player: Player, recursion_depth: usize, line_count: usize, weights: &Weights) -> Vec<AnalysisLine> {
+
+    let mut lines = Vec::new();
+
+    let mut start = (0, 0);
+    loop {
+
+        let mut new_board = board.clone();
+
+        let Some((row, col)) = search.referee.find_and_apply_next_valid_move(&mut new_board, player, start) else {
+            break;
+        };
+        start = (row, col + 1);
+
+        let mut principal_variation = vec![Action::Place((row, col))];
+
+        let score = if recursion_depth <= 1 {
+
+            evaluation::evaluate_board(&mut search.referee, &mut search.valid_moves, &new_board, player, weights)
+
+        } else if board.is_rolit() {
+
+            let mut next_variation = Vec::new();
+            let (next_move, next_scores) = find_best_move_max_n(search, &new_board, board.next_player(player), recursion_depth - 1, weights, &mut next_variation);
+            principal_variation.extend(next_variation);
+
+            // Rolit has no passes, so the game is over if the next player can't move
+            if next_move.is_some() { next_scores[player as usize] } else { evaluate_scores(search, &new_board, weights)[player as usize] }
+
+        } else {
+
+            let mut opponent_variation = Vec::new();
+            let (_opponent_move, opponent_score) = find_best_move_recursive(search, &new_board, player.opponent(), recursion_depth - 1, weights, &mut opponent_variation);
+            principal_variation.extend(opponent_variation);
+            -opponent_score
+        };
+
+        // a stopped analysis only reports the moves that were fully analyzed
+        if search.is_stopped() {
+            break;
+        }
+
+        lines.push(AnalysisLine { next_move: (row, col), score, principal_variation });
+    }
+
+    lines.sort_by(|a, b| b.score.total_cmp(&a.score));
+    lines.truncate(line_count);
+
+    lines
+}
+
+// returns (the optimal move, evaluation score given to that move), where the move is None if there is no valid move,
+// in which case the score is that of passing
+// principal_variation is filled with the optimal move followed by the expected replies, passes included
+// TODO: add alpha-beta pruning
+// TODO: it's silly to think very hard about the first few moves
+fn find_best_move_recursive(search: &mut SearchContext, board: &Board, player: Player, recursion_depth: usize, weights: &Weights, principal_variation: &mut Vec<Action>) -> (Option<Move>, f32) {
+    let mut optimal_move = None;
+    let mut optimal_score = f32::NEG_INFINITY;
+    let mut selection_count = 0; // Track number of equally good moves found
+    let mut child_variation = Vec::new();
+
+    principal_variation.clear();
+
+    let mut start = (0, 0);
+    loop {
+
+        let mut new_board = board.clone();
+
+        let Some((row, col)) = search.referee.find_and_apply_next_valid_move(&mut new_board, player, start) else {
+            break;
+        };
+        start = (row, col + 1);
+
+        search.count_node();
+
+        child_variation.clear();
+
+        // the evaluated score of this potential move is...
+        let board_score = 
+            // ...(depending on how far we want to think into the future)...
+            if recursion_depth == 1 {
+
+                // ...either how good it would make the board for us now...
+                evaluation::evaluate_board(&mut search.referee, &mut search.valid_moves, &new_board, player, weights)
+
+            } else {
+
+                // ...or how good the board will become if the opponent makes their best move next...
+                let (_opponent_move, opponent_score) = find_best_move_recursive(search, &new_board, player.opponent(), recursion_depth - 1, weights, &mut child_variation);
+
+                // ...and since this is a symmetric, zero-sum game,
+                // how good it is for us is the inverse of how good it is for them
+                -opponent_score
+            };
+
+        if search.is_stopped() {
+
+            // the search of this move was cut short, so its score can't be trusted,
+            // but any move is better than no move
+            if optimal_move.is_none() {
+
+                optimal_score = board_score;
+                optimal_move = Some((row, col));
+
+                principal_variation.clear();
+                principal_variation.push(Action::Place((row, col)));
+            }
+
+            break;
+        }
+
+        let is_new_optimum = if optimal_move.is_none() {
+
+            // any move is better than no move
+            selection_count = 1;
+            true
+
+        } else if board_score == optimal_score {
+
+            // online reservoir sampling ensures equally good moves have equal chance of getting picked
+            selection_count += 1;
+            let replacement_probability = 1.0 / selection_count as f64;
+            search.rng.random_bool(replacement_probability)
+
+        } else if board_score > optimal_score {
+
+            // this is for sure the best move so far
+            selection_count = 1;
+            true
+
+        } else {
+
+            false
+        };
+
+        if is_new_optimum {
+
+            optimal_score = board_score;
+            optimal_move = Some((row, col));
+
+            principal_variation.clear();
+            principal_variation.push(Action::Place((row, col)));
+            principal_variation.extend_from_slice(&child_variation);
+        }
+    }
+
+    if optimal_move.is_none() {
+
+        // without a valid move the player passes, and the opponent moves again,
+        // unless the opponent can't move either, and the game is over
+        optimal_score = if recursion_depth > 1 && search.referee.find_all_valid_moves(board, player.opponent(), &mut search.valid_moves) {
+
+            let (_opponent_move, opponent_score) = find_best_move_recursive(search, board, player.opponent(), recursion_depth - 1, weights, &mut child_variation);
+            principal_variation.push(Action::Pass);
+            principal_variation.extend_from_slice(&child_variation);
+            -opponent_score
+
+        } else {
+
+            evaluation::evaluate_board(&mut search.referee, &mut search.valid_moves, board, player, weights)
+        };
+    }
+
+    (optimal_move, optimal_score)
+}
+
+// every player's own evaluation of the board
+fn evaluate_scores(search: &mut SearchContext, board: &Board, weights: &Weights) -> Scores {
+
+    let mut scores = [0.0; Player::COUNT];
+    for player in board.players() {
+
+        scores[player as usize] = evaluation::evaluate_board(&mut search.referee, &mut search.valid_moves, board, player, weights);
+    }
+
+    scores
+}
+
+// the max-n counterpart of find_best_move_recursive, for more than two players,
+// where every player picks the move that is best for themselves, going by their own score
+// returns (the optimal move, the scores of all players for that move)
+fn find_best_move_max_n(search: &mut SearchContext, board: &Board, player: Player, recursion_depth: usize, weights: &Weights, principal_variation: &mut Vec<Action>) -> (Option<Move>, Scores) {
+
+    let mut optimal_move = None;
+    let mut optimal_scores = [f32::NEG_INFINITY; Player::COUNT];
+    let mut selection_count = 0;
+    let mut child_variation = Vec::new();
+
+    principal_variation.clear();
+
+    let mut start = (0, 0);
+    loop {
+
+        let mut new_board = board.clone();
+
+        let Some((row, col)) = search.referee.find_and_apply_next_valid_move(&mut new_board, player, start) else {
+            break;
+        };
+        start = (row, col + 1);
+
+        search.count_node();
+
+        child_variation.clear();
+
+        let scores = if recursion_depth == 1 {
+
+            evaluate_scores(search, &new_board, weights)
+
+        } else {
+
+            let (next_move, next_scores) = find_best_move_max_n(search, &new_board, new_board.next_player(player), recursion_depth - 1, weights, &mut child_variation);
+
+            // Rolit has no passes, so the game is over if the next player can't move
+            if next_move.is_some() { next_scores } else { evaluate_scores(search, &new_board, weights) }
+        };
+
+        let score = scores[player as usize];
+        let optimal_score = optimal_scores[player as usize];
+
+        let is_new_optimum = if optimal_move.is_none() {
+
+            // any move is better than no move, even if the search was cut short
+            selection_count = 1;
+            true
+
+        } else if search.is_stopped() {
+
+            false
+
+        } else if score == optimal_score {
+
+            // online reservoir sampling ensures equally good moves have equal chance of getting picked
+            selection_count += 1;
+            search.rng.random_bool(1.0 / selection_count as f64)
+
+        } else if score > optimal_score {
+
+            selection_count = 1;
+            true
+
+        } else {
+
+            false
+        };
+
+        if is_new_optimum {
+
+            optimal_scores = scores;
+            optimal_move = Some((row, col));
+
+            principal_variation.clear();
+            principal_variation.push(Action::Place((row, col)));
+            principal_variation.extend_from_slice(&child_variation);
+        }
+
+        if search.is_stopped() {
+            break;
+        }
+    }
+
+    (optimal_move, optimal_scores)
+}
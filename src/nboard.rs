@@ -0,0 +1,341 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::process::Child;
+use std::process::ChildStdin;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::board::Action;
+use crate::board::Board;
+use crate::board::Cell;
+use crate::board::Player;
+use crate::board::Ruleset;
+use crate::board::Topology;
+use crate::strategy::Parameter;
+use crate::strategy::SearchContext;
+use crate::strategy::Strategy;
+
+// Helpers for the NBoard protocol, a line-based text protocol for Othello engines
+// the engine reads commands from stdin and writes replies to stdout
+
+// the move notation of the protocol, e.g. "F5" for (row 4, col 5), "PA" for pass
+pub fn format_move(action: Action) -> String {
+
+    match action {
+        Action::Place((row, col)) => format!("{}{}", (b'A' + col as u8) as char, row + 1),
+        Action::Pass => "PA".to_string(),
+    }
+}
+
+pub fn parse_move(text: &str, board_size: usize) -> Option<Action> {
+
+    let text = text.trim().to_ascii_uppercase();
+    if text == "PA" {
+
+        return Some(Action::Pass);
+    }
+
+    let mut chars = text.chars();
+    let col = chars.next()? as usize;
+    let row: usize = chars.as_str().parse().ok()?;
+
+    if (b'A' as usize..b'A' as usize + board_size).contains(&col) && (1..=board_size).contains(&row) {
+
+        Some(Action::Place((row - 1, col - b'A' as usize)))
+
+    } else {
+
+        None
+    }
+}
+
+// a game in the GGF format, starting from the given position, without any moves played
+// None if the format can't describe the position, since it only knows two players and no blocked cells
+pub fn format_game(board: &Board, player: Player) -> Option<String> {
+
+    let mut position = String::new();
+    for row in 0..board.size() {
+        for col in 0..board.size() {
+
+            position.push(match board.grid[row][col] {
+                Cell::Empty => '-',
+                Cell::Taken(Player::Black) => '*',
+                Cell::Taken(Player::White) => 'O',
+                Cell::Taken(Player::Red | Player::Blue) | Cell::Blocked => return None,
+            });
+        }
+    }
+
+    let side = match player {
+        Player::Black => '*',
+        Player::White => 'O',
+        Player::Red | Player::Blue => return None,
+    };
+
+    Some(format!("(;GM[Othello]PC[othello]TY[{size}]BO[{size} {position} {side}];)", size = board.size()))
+}
+
+// reads the starting position of a GGF game
+// moves after the starting position are not supported
+pub fn parse_game(text: &str) -> Option<(Board, Player)> {
+
+    let start = text.find("BO[")? + "BO[".len();
+    let end = start + text[start..].find(']')?;
+    let mut fields = text[start..end].split_whitespace();
+
+    let size: usize = fields.next()?.parse().ok()?;
+    let position = fields.next()?;
+    let side = fields.next()?;
+
+    if !size.is_multiple_of(2) || !(Board::MIN_SIZE..=Board::MAX_SIZE).contains(&size) || position.chars().count() != size * size {
+
+        return None;
+    }
+
+    let mut board = Board::empty(size);
+    for (i, c) in position.chars().enumerate() {
+
+        board.grid[i / size][i % size] = match c {
+            '-' => Cell::Empty,
+            '*' => Cell::Taken(Player::Black),
+            'O' => Cell::Taken(Player::White),
+            _ => return None,
+        };
+    }
+
+    let player = match side {
+        "*" => Player::Black,
+        "O" => Player::White,
+        _ => return None,
+    };
+
+    Some((board, player))
+}
+
+// how often a waiting engine checks whether the search was stopped
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+// a running engine subprocess
+struct EngineProcess {
+
+    command_line: String,
+    child: Child,
+    stdin: ChildStdin,
+    // the lines of the engine's output, read on a thread of their own,
+    // so that waiting for an engine that doesn't reply can be given up on
+    lines: mpsc::Receiver<String>,
+    ping_count: u32,
+}
+
+impl EngineProcess {
+
+    // the command line is split on whitespace, so paths with spaces are not supported
+    fn spawn(command_line: &str) -> std::io::Result<Self> {
+
+        let mut words = command_line.split_whitespace();
+        let program = words.next().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "empty engine command line"))?;
+
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+
+        // the thread ends when the engine closes its output, which it does when it's killed
+        let (line_sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+
+            for line in stdout.lines() {
+
+                let Ok(line) = line else {
+                    break;
+                };
+                if line_sender.send(line.trim().to_string()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut engine = EngineProcess { command_line: command_line.to_string(), child, stdin, lines, ping_count: 0 };
+        engine.send("nboard 2")?;
+
+        Ok(engine)
+    }
+
+    fn send(&mut self, command: &str) -> std::io::Result<()> {
+
+        writeln!(self.stdin, "{command}")?;
+        self.stdin.flush()
+    }
+
+    // gives up with an Interrupted error once the search is stopped
+    fn receive(&mut self, search: &SearchContext) -> std::io::Result<String> {
+
+        loop {
+
+            match self.lines.recv_timeout(STOP_CHECK_INTERVAL) {
+                Ok(line) => return Ok(line),
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if search.is_stopped() {
+                        return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "the search was stopped"));
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "engine closed its output"));
+                }
+            }
+        }
+    }
+
+    // skips any output left over from earlier commands
+    fn synchronize(&mut self, search: &SearchContext) -> std::io::Result<()> {
+
+        self.ping_count += 1;
+        let pong = format!("pong {}", self.ping_count);
+        self.send(&format!("ping {}", self.ping_count))?;
+
+        while self.receive(search)? != pong {}
+
+        Ok(())
+    }
+
+    // the engine replies with "=== <move>[/<score>[/<time>]]" when it's done thinking
+    // the game must be one that format_game can describe
+    fn find_move(&mut self, search: &SearchContext, game: &str, board_size: usize, depth: i32) -> std::io::Result<Option<Action>> {
+
+        self.synchronize(search)?;
+        self.send(&format!("set depth {depth}"))?;
+        self.send(&format!("set game {game}"))?;
+        self.send("go")?;
+
+        loop {
+
+            let line = self.receive(search)?;
+            if let Some(reply) = line.strip_prefix("===") {
+
+                let move_text = reply.trim().split('/').next().unwrap_or("");
+                return Ok(parse_move(move_text, board_size));
+            }
+        }
+    }
+}
+
+impl Drop for EngineProcess {
+
+    fn drop(&mut self) {
+
+        // ask nicely first
+        let _ = self.send("quit");
+        if !matches!(self.child.try_wait(), Ok(Some(_))) {
+
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+// lets an external engine choose the moves, by speaking the NBoard protocol with it
+// the engine is started on the first move and kept running until the command line changes
+// the protocol can't interrupt a search, so stopping kills the engine, which is started again for the next move
+#[derive(Default)]
+pub struct ExternalEngineStrategy {
+
+    engine: Option<EngineProcess>,
+}
+
+impl Strategy for ExternalEngineStrategy {
+
+    fn name(&self) -> &'static str {
+
+        "External Engine"
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+
+        vec![
+            Parameter::text("Command Line", ""),
+            Parameter::integer("Depth", 8, 1, 60),
+            // separated by spaces, since most engines only play on the standard board
+            Parameter::text("Board Sizes", "8"),
+        ]
+    }
+
+    fn label(&self, parameters: &[Parameter]) -> String {
+
+        format!("Engine {} lvl {}", parameters[0].as_text(), parameters[1].as_integer())
+    }
+
+    // once stopped, it plays the first valid move, since the engine doesn't report a best move so far
+    fn choose_move(&mut self, search: &mut SearchContext, board: &Board, player: Player, parameters: &[Parameter]) -> Action {
+
+        let command_line = parameters[0].as_text();
+        let depth = parameters[1].as_integer();
+        let board_sizes = parameters[2].as_text();
+
+        // the protocol has no way to tell the engine about other rules
+        if board.is_rolit() || board.is_reversi() || board.topology != Topology::Flat || board.ruleset != Ruleset::Standard {
+
+            eprintln!("Engine '{command_line}' only plays Othello");
+            return Action::Pass;
+        }
+
+        // the GGF format has no blocked cells
+        let Some(game) = format_game(board, player) else {
+
+            eprintln!("Engine '{command_line}' doesn't play on boards with holes");
+            return Action::Pass;
+        };
+
+        if !board_sizes.split_whitespace().any(|size| size.parse() == Ok(board.size())) {
+
+            eprintln!("Engine '{command_line}' doesn't play on a {0}x{0} board", board.size());
+            return Action::Pass;
+        }
+
+        if self.engine.as_ref().is_none_or(|engine| engine.command_line != command_line) {
+
+            self.engine = match EngineProcess::spawn(command_line) {
+                Ok(engine) => Some(engine),
+                Err(e) => {
+                    eprintln!("Unable to start engine '{command_line}': {e}");
+                    None
+                }
+            };
+        }
+
+        let Some(engine) = &mut self.engine else {
+
+            return Action::Pass;
+        };
+
+        match engine.find_move(search, &game, board.size(), depth) {
+
+            // don't trust the engine blindly
+            Ok(Some(action)) if search.referee.validate_action(board, player, action) => action,
+            Ok(reply) => {
+                eprintln!("Engine '{command_line}' replied with an invalid move: {reply:?}");
+                Action::Pass
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                self.engine = None;
+                if search.referee.find_all_valid_moves(board, player, &mut search.valid_moves) {
+                    Action::Place(search.valid_moves.get(0))
+                } else {
+                    Action::Pass
+                }
+            }
+            Err(e) => {
+                eprintln!("Lost contact with engine '{command_line}': {e}");
+                self.engine = None;
+                Action::Pass
+            }
+        }
+    }
+}
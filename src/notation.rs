@@ -0,0 +1,109 @@
+use crate::board::Action;
+use crate::common::CellList;
+use crate::record::GameRecord;
+use crate::record::Turn;
+use crate::referee::Referee;
+
+type Move = (usize, usize);
+
+// Algebraic notation: a column letter followed by a row number, where "a1" is the top left cell,
+// and transcripts, which are the moves of a game run together like "f5d6c3d3c4", leaving out the passes
+
+pub fn format_move((row, col): Move) -> String {
+
+    format!("{}{}", (b'a' + col as u8) as char, row + 1)
+}
+
+pub fn format_action(action: Action) -> String {
+
+    match action {
+        Action::Place(next_move) => format_move(next_move),
+        Action::Pass => "pass".to_string(),
+    }
+}
+
+// either case, None if it's not a cell of the board
+pub fn parse_move(text: &str, board_size: usize) -> Option<Move> {
+
+    let text = text.trim().to_ascii_lowercase();
+    let mut chars = text.chars();
+    let col = chars.next().filter(char::is_ascii_lowercase)? as usize - 'a' as usize;
+    let digits = chars.as_str();
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+
+        return None;
+    }
+
+    let row: usize = digits.parse().ok()?;
+    (col < board_size && (1..=board_size).contains(&row)).then(|| (row - 1, col))
+}
+
+pub fn format_transcript(turns: &[Turn]) -> String {
+
+    turns.iter().filter_map(|turn| match turn.action {
+        Action::Place(next_move) => Some(format_move(next_move)),
+        Action::Pass => None,
+    }).collect()
+}
+
+// a letter followed by the digits after it, so that rows past 9 on larger boards work out
+// whitespace in between moves is ignored, anything else ends up as a move of its own
+fn split_transcript(text: &str) -> Vec<String> {
+
+    let mut moves: Vec<String> = Vec::new();
+
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+
+        match moves.last_mut() {
+            Some(last) if c.is_ascii_digit() => last.push(c),
+            _ => moves.push(c.to_string()),
+        }
+    }
+
+    moves
+}
+
+// plays the moves of the transcript on from the current position of the record, making the passes in between
+// returns what's wrong with the first bad move, in which case the record is left at the move before it
+pub fn play_transcript(record: &mut GameRecord, transcript: &str) -> Result<(), String> {
+
+    let mut referee = Referee::default();
+    let mut flip_cells = CellList::default();
+    let (mut board, mut player) = record.replay(&mut referee);
+
+    for (index, text) in split_transcript(transcript).iter().enumerate() {
+
+        let Some(next_move) = parse_move(text, board.size()) else {
+
+            return Err(format!("Move {} ({text}) is not a cell of the board", index + 1));
+        };
+
+        while referee.is_pass_forced(&board, player) {
+
+            record.push(Turn { player, action: Action::Pass });
+            player = board.next_player(player);
+        }
+
+        if !referee.find_flip_cells_for_move(&board, player, next_move, &mut flip_cells) {
+
+            return Err(format!("Move {} ({text}) is not valid for {player:?}", index + 1));
+        }
+
+        Referee::apply_move(&mut board, player, next_move, &flip_cells);
+        record.push(Turn { player, action: Action::Place(next_move) });
+        player = board.next_player(player);
+    }
+
+    Ok(())
+}
+
+// for the status display, e.g. "White played d3"
+pub fn describe_turn(turn: &Turn) -> String {
+
+    let Turn { player, action } = turn;
+    match action {
+        Action::Place(next_move) => format!("{player:?} played {}", format_move(*next_move)),
+        Action::Pass => format!("{player:?} passed"),
+    }
+}
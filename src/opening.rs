@@ -0,0 +1,64 @@
+use rand::Rng;
+
+use crate::board::Board;
+use crate::board::Player;
+use crate::notation;
+use crate::record::GameRecord;
+use crate::referee::Referee;
+
+// How a game starts
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StartingPosition {
+    // the standard start, with the four centre discs placed diagonally
+    Diagonal,
+    Parallel,
+    // a position in the format of Board::from_position_text, or of Board::from_text with black to move
+    Text(String),
+    // one of the balanced openings of the list, drawn at random
+    // they're for the standard board size only
+    Xot,
+}
+
+// one opening per line, as a sequence of moves like "f5d6c3d3c4f4f6f3", from the standard start
+const XOT_OPENINGS: &str = include_str!("openings.txt");
+
+pub fn xot_openings() -> impl Iterator<Item = &'static str> {
+
+    XOT_OPENINGS.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+// returns the board and the player to move, or what's wrong with the starting position
+pub fn set_up(start: &StartingPosition, board_size: usize, rng: &mut impl Rng) -> Result<(Board, Player), String> {
+
+    match start {
+
+        StartingPosition::Diagonal => Ok((Board::new(board_size), Player::Black)),
+        StartingPosition::Parallel => Ok((Board::new_parallel(board_size), Player::Black)),
+        StartingPosition::Text(text) => match Board::from_position_text(text) {
+            Some(position) => Ok(position),
+            None => Board::from_text(text).map(|board| (board, Player::Black)).ok_or_else(|| "Invalid position text".to_string()),
+        },
+        StartingPosition::Xot => {
+
+            if board_size != Board::STANDARD_SIZE {
+
+                return Err(format!("XOT openings need a {0}x{0} board", Board::STANDARD_SIZE));
+            }
+
+            let openings: Vec<&str> = xot_openings().collect();
+            let opening = openings[rng.random_range(..openings.len())];
+            play_opening(opening).map_err(|e| format!("Invalid opening {opening}: {e}"))
+        }
+    }
+}
+
+// plays the moves of an opening from the standard start
+// returns what's wrong with the first invalid move, if there is one
+pub fn play_opening(opening: &str) -> Result<(Board, Player), String> {
+
+    let mut record = GameRecord::new(Board::default(), Player::Black, 0);
+    notation::play_transcript(&mut record, opening)?;
+
+    Ok(record.replay(&mut Referee::default()))
+}
@@ -0,0 +1,62 @@
+use crate::board::Board;
+use crate::board::Player;
+use crate::common::CellList;
+use crate::referee::Referee;
+
+// counts the leaf nodes of the game tree to the given depth, for verifying move generation
+// like the standard Othello perft, a pass counts as a move, and a finished game counts as a leaf
+pub fn perft(referee: &mut Referee, board: &Board, player: Player, depth: usize) -> u64 {
+
+    if depth == 0 {
+
+        return 1;
+    }
+
+    let mut valid_moves = CellList::default();
+    if !referee.find_all_valid_moves(board, player, &mut valid_moves) {
+
+        if !referee.find_all_valid_moves(board, player.opponent(), &mut valid_moves) {
+
+            // the game is over
+            return 1;
+        }
+
+        return perft(referee, board, player.opponent(), depth - 1);
+    }
+
+    let mut flip_cells = CellList::default();
+    let mut leaf_count = 0;
+
+    for next_move in valid_moves.iter() {
+
+        let mut next_board = board.clone();
+        referee.find_flip_cells_for_move(board, player, next_move, &mut flip_cells);
+        Referee::apply_move(&mut next_board, player, next_move, &flip_cells);
+
+        leaf_count += perft(referee, &next_board, player.opponent(), depth - 1);
+    }
+
+    leaf_count
+}
+
+// the leaf counts after each of the first moves, which helps narrowing down where two move generators differ
+pub fn divide(referee: &mut Referee, board: &Board, player: Player, depth: usize) -> Vec<((usize, usize), u64)> {
+
+    let mut valid_moves = CellList::default();
+    if depth == 0 || !referee.find_all_valid_moves(board, player, &mut valid_moves) {
+
+        return Vec::new();
+    }
+
+    let mut flip_cells = CellList::default();
+
+    valid_moves.iter().map(|next_move| {
+
+        let mut next_board = board.clone();
+        referee.find_flip_cells_for_move(board, player, next_move, &mut flip_cells);
+        Referee::apply_move(&mut next_board, player, next_move, &flip_cells);
+
+        (next_move, perft(referee, &next_board, player.opponent(), depth - 1))
+
+    }).collect()
+}
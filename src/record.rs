@@ -0,0 +1,148 @@
+use crate::board::Action;
+use crate::board::Board;
+use crate::board::Player;
+use crate::common::CellList;
+use crate::referee::Referee;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Turn {
+    pub player: Player,
+    pub action: Action,
+}
+
+impl Turn {
+
+    pub fn is_move(&self) -> bool {
+
+        matches!(self.action, Action::Place(_))
+    }
+}
+
+// everything it takes to replay a game: the starting position, the seed all agent decisions derive from, and every turn,
+// passes included, but not the passes of a finished game
+// the turns past the current position have been undone, and can be redone until another turn is played
+#[derive(Clone)]
+pub struct GameRecord {
+
+    pub start: Board,
+    pub first_player: Player,
+    pub seed: u64,
+    turns: Vec<Turn>,
+    position: usize,
+}
+
+impl GameRecord {
+
+    pub fn new(start: Board, first_player: Player, seed: u64) -> Self {
+
+        GameRecord { start, first_player, seed, turns: Vec::new(), position: 0 }
+    }
+
+    // the turns up to the current position
+    pub fn turns(&self) -> &[Turn] {
+
+        &self.turns[..self.position]
+    }
+
+    pub fn undone_turns(&self) -> &[Turn] {
+
+        &self.turns[self.position..]
+    }
+
+    // the undone turns are gone for good
+    pub fn push(&mut self, turn: Turn) {
+
+        self.turns.truncate(self.position);
+        self.turns.push(turn);
+        self.position += 1;
+    }
+
+    // the number of moves up to the current position, passes not included
+    pub fn move_count(&self) -> usize {
+
+        self.turns().iter().filter(|turn| turn.is_move()).count()
+    }
+
+    pub fn can_undo(&self, is_human: impl Fn(Player) -> bool) -> bool {
+
+        self.last_human_move(is_human).is_some()
+    }
+
+    pub fn can_redo(&self) -> bool {
+
+        self.undone_turns().iter().any(Turn::is_move)
+    }
+
+    // takes back the last move of a human player, along with every turn after it,
+    // so that undoing against the AI takes back the moves of both sides
+    pub fn undo(&mut self, is_human: impl Fn(Player) -> bool) -> bool {
+
+        match self.last_human_move(is_human) {
+            Some(index) => {
+                self.position = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // the counterpart of undo, which plays on until it's a human player's move again, or until the last move
+    // the passes after the last move are left undone, since they're made again when the next turn begins
+    pub fn redo(&mut self, is_human: impl Fn(Player) -> bool) -> bool {
+
+        if !self.can_redo() {
+
+            return false;
+        }
+
+        let next_human_move = {
+            let is_human_move = self.human_move_filter(is_human);
+            self.turns.iter().skip(self.position + 1).position(is_human_move).map(|offset| self.position + 1 + offset)
+        };
+        let last_move = self.turns.iter().rposition(Turn::is_move);
+
+        self.position = match (next_human_move, last_move) {
+            (Some(index), _) => index,
+            (None, Some(index)) => index + 1,
+            (None, None) => unreachable!("there is a move to redo"),
+        };
+
+        true
+    }
+
+    // returns the board at the current position and the player whose turn it is next, not taking passes into account
+    pub fn replay(&self, referee: &mut Referee) -> (Board, Player) {
+
+        let mut board = self.start.clone();
+        let mut flip_cells = CellList::default();
+
+        for turn in self.turns() {
+
+            assert!(referee.validate_action(&board, turn.player, turn.action), "the record has an invalid turn {turn:?}");
+            if let Action::Place(next_move) = turn.action {
+
+                referee.find_flip_cells_for_move(&board, turn.player, next_move, &mut flip_cells);
+                Referee::apply_move(&mut board, turn.player, next_move, &flip_cells);
+            }
+        }
+
+        let player = match self.turns().last() {
+            Some(turn) => board.next_player(turn.player),
+            None => self.first_player,
+        };
+
+        (board, player)
+    }
+
+    fn last_human_move(&self, is_human: impl Fn(Player) -> bool) -> Option<usize> {
+
+        self.turns().iter().rposition(self.human_move_filter(is_human))
+    }
+
+    // without human players, any move will do
+    fn human_move_filter(&self, is_human: impl Fn(Player) -> bool) -> impl Fn(&Turn) -> bool {
+
+        let has_human = self.start.players().any(&is_human);
+        move |turn| turn.is_move() && (!has_human || is_human(turn.player))
+    }
+}
@@ -1,57 +1,160 @@
+use std::fmt::Display;
+
+use crate::board::Action;
 use crate::board::Cell;
 use crate::board::Board;
 use crate::board::Player;
+use crate::board::Ruleset;
+use crate::board::Topology;
 use crate::common::CellList;
+use crate::bitboard;
+use crate::bitboard::BitBoard;
+
+// the final disc count of every player, indexed by player, with the empty squares handed out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub discs: [i32; Player::COUNT],
+    pub player_count: usize,
+    pub ruleset: Ruleset,
+}
+
+impl Score {
 
+    // how many discs the player is ahead of the best of the others, negative if the player is behind,
+    // where being ahead means having fewer discs under misère rules
+    pub fn margin(&self, player: Player) -> i32 {
+
+        let others = (0..self.player_count).filter(|&other| other != player as usize).map(|other| self.discs[other]);
+        match self.ruleset {
+            Ruleset::Standard => self.discs[player as usize] - others.max().unwrap_or_default(),
+            Ruleset::Misere => others.min().unwrap_or_default() - self.discs[player as usize],
+        }
+    }
+}
+
+// like 33-31, in the order of turns
+impl Display for Score {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+
+        let discs: Vec<String> = self.discs[..self.player_count].iter().map(|count| count.to_string()).collect();
+        write!(f, "{}", discs.join("-"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Outcome {
-    Won(Player),
-    Tie,
+    Won(Player, Score),
+    Tie(Score),
+}
+
+impl Outcome {
+
+    pub fn score(&self) -> &Score {
+
+        match self {
+            Outcome::Won(_, score) | Outcome::Tie(score) => score,
+        }
+    }
 }
 
 type Move = (usize, usize);
 
+// the steps to the eight neighbours of a cell, in row-major order
+const DIRECTIONS: [(i32, i32); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+// how the referee finds valid moves and flips
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Representation {
+    // shift-based, on two 64-bit masks, which is a lot faster
+    // only for the standard board size, other sizes fall back on the grid
+    #[default]
+    BitBoard,
+    // cell by cell, on the grid of the board
+    Grid,
+}
+
+// the kind of moves a player can make, which depends on the rules and the position
+#[derive(Clone, Copy)]
+enum MoveKind {
+    // out of discs
+    None,
+    // the opening of the original Reversi rules, which fills the centre without capturing
+    CentrePlacement,
+    Capture,
+    // Rolit, without a capturing move a disk can be placed on any empty cell next to another disk
+    Placement,
+}
+
 // not thread-safe, every thread needs its own Referee
+#[derive(Default)]
 pub struct Referee {
 
+    representation: Representation,
+
     // a cache for the result of find_adjacent_opposites
     adjacent_opposites: CellList,
 
     // a cache for the result of find_flip_cells
     flip_cells: CellList,
-}
-
-impl Default for Referee {
 
-    fn default() -> Self {
-
-        Referee {
-            adjacent_opposites: CellList::default(),
-            flip_cells: CellList::default(),
-        }
-    }
+    // a cache for the result of has_capturing_move, as (the board, the player, the result)
+    // a search tries every move on the same board, and every try needs to know the kind of move
+    capturing_move: Option<(Board, Player, bool)>,
 }
 
 impl Referee {
     
     // public
+    pub fn with_representation(representation: Representation) -> Self {
+
+        Referee { representation, ..Default::default() }
+    }
+
     pub fn validate_move(&mut self, board: &Board, player: Player, maybe_move: Move) -> bool {
 
-        Self::find_flip_cells_for_move_internal(board, player, maybe_move, &mut self.adjacent_opposites, &mut self.flip_cells)
+        if self.uses_bitboard(board) {
+
+            board.contains(maybe_move) && BitBoard::from_board(board).find_flips(player, maybe_move) != 0
+
+        } else {
+
+            let move_kind = self.find_move_kind(board, player);
+            Self::find_flip_cells_for_move_of_kind(move_kind, board, player, maybe_move, &mut self.adjacent_opposites, &mut self.flip_cells)
+        }
     }
 
+    // the order of the flip cells depends on the representation
+    // a valid move doesn't flip anything if it's a placement
     pub fn find_flip_cells_for_move(&mut self, board: &Board, player: Player, maybe_move: Move, result: &mut CellList) -> bool {
         
-        Self::find_flip_cells_for_move_internal(board, player, maybe_move, &mut self.adjacent_opposites, result)
+        if self.uses_bitboard(board) {
+
+            let flips = if board.contains(maybe_move) { BitBoard::from_board(board).find_flips(player, maybe_move) } else { 0 };
+            Self::collect_cells(flips, result)
+
+        } else {
+
+            let move_kind = self.find_move_kind(board, player);
+            Self::find_flip_cells_for_move_of_kind(move_kind, board, player, maybe_move, &mut self.adjacent_opposites, result)
+        }
     }
 
+    // the moves are in row-major order
     pub fn find_all_valid_moves(&mut self, board: &Board, player: Player, result: &mut CellList) -> bool {
         
+        if self.uses_bitboard(board) {
+
+            return Self::collect_cells(BitBoard::from_board(board).find_valid_moves(player), result);
+        }
+
+        let move_kind = self.find_move_kind(board, player);
         result.count = 0;
 
-        for row in 0..Board::SIZE {
-            for col in 0..Board::SIZE {
+        for row in 0..board.size() {
+            for col in 0..board.size() {
 
-                if self.validate_move(board, player, (row, col)) {
+                if Self::find_flip_cells_for_move_of_kind(move_kind, board, player, (row, col), &mut self.adjacent_opposites, &mut self.flip_cells) {
                     
                     result.push_back((row, col));
                 }
@@ -61,59 +164,117 @@ impl Referee {
         result.count != 0
     }
 
-    // returns (player disk count, opponent disk count)
+    // a player without a valid move has to pass, unless nobody has one and the game is over
+    pub fn is_pass_forced(&mut self, board: &Board, player: Player) -> bool {
+
+        let mut valid_moves = CellList::default();
+
+        !self.find_all_valid_moves(board, player, &mut valid_moves)
+            && board.players().any(|other| other != player && self.find_all_valid_moves(board, other, &mut valid_moves))
+    }
+
+    // the valid moves, or a lone pass if the player has to pass
+    // returns false if the game is over
+    pub fn find_all_valid_actions(&mut self, board: &Board, player: Player, result: &mut Vec<Action>) -> bool {
+
+        let mut valid_moves = CellList::default();
+        result.clear();
+
+        if self.find_all_valid_moves(board, player, &mut valid_moves) {
+
+            result.extend(valid_moves.iter().map(Action::Place));
+
+        } else if self.is_pass_forced(board, player) {
+
+            result.push(Action::Pass);
+        }
+
+        !result.is_empty()
+    }
+
+    pub fn validate_action(&mut self, board: &Board, player: Player, action: Action) -> bool {
+
+        match action {
+            Action::Place(maybe_move) => self.validate_move(board, player, maybe_move),
+            Action::Pass => self.is_pass_forced(board, player),
+        }
+    }
+
+    // returns (player disk count, disk count of everyone else)
     pub fn count_disks(board: &Board, player: Player) -> (i32, i32) {
 
-        let mut black_count = 0;
-        let mut white_count = 0;
+        let counts = Self::count_disks_per_player(board);
+        let player_count = counts[player as usize];
 
-        for row in 0..Board::SIZE {
-            for col in 0..Board::SIZE {
+        (player_count, counts.iter().sum::<i32>() - player_count)
+    }
+
+    // indexed by player
+    pub fn count_disks_per_player(board: &Board) -> [i32; Player::COUNT] {
+
+        let mut counts = [0; Player::COUNT];
 
-                match board.grid[row][col] {
+        for row in 0..board.size() {
+            for col in 0..board.size() {
 
-                    Cell::Empty => {},
-                    Cell::Taken(Player::Black) => black_count += 1,
-                    Cell::Taken(Player::White) => white_count += 1,
+                if let Cell::Taken(player) = board.grid[row][col] {
+
+                    counts[player as usize] += 1;
                 }
             }
         }
 
-        match player {
-            Player::Black => (black_count, white_count),
-            Player::White => (white_count, black_count),
-        }
+        counts
     }
 
+    // a tie if several players share the best score
+    // scored the way the World Othello Federation does, where the empty squares go to the winner,
+    // or are split between the players sharing a tie
     pub fn check_outcome(board: &Board) -> Outcome {
 
-        let (black_count, white_count) = Self::count_disks(board, Player::Black);
+        let counts = Self::count_disks_per_player(board);
 
-        if black_count > white_count {
+        // fewer discs is better under misère rules
+        let score = |player: Player| match board.ruleset {
+            Ruleset::Standard => counts[player as usize],
+            Ruleset::Misere => -counts[player as usize],
+        };
 
-            Outcome::Won(Player::Black)
+        let best_score = board.players().map(score).max().unwrap_or_default();
+        let winners: Vec<Player> = board.players().filter(|&player| score(player) == best_score).collect();
+        let score = Self::hand_out_empty_squares(board, &winners);
 
-        } else if white_count > black_count {
+        match winners[..] {
+            [winner] => Outcome::Won(winner, score),
+            _ => Outcome::Tie(score),
+        }
+    }
 
-            Outcome::Won(Player::White)
+    // for a player that breaks the rules, the game is lost no matter the discs on the board
+    pub fn forfeit(board: &Board, winner: Player) -> Outcome {
 
-        } else {
-            
-            Outcome::Tie
-        }
+        Outcome::Won(winner, Self::hand_out_empty_squares(board, &[winner]))
     }
 
-    pub fn find_and_apply_next_valid_move(&mut self, board: &mut Board, player: Player, (start_row, start_col): Move) -> Move {
+    // the first valid move from the start cell onwards, in row-major order, None if there is none
+    pub fn find_and_apply_next_valid_move(&mut self, board: &mut Board, player: Player, (start_row, start_col): Move) -> Option<Move> {
+
+        if self.uses_bitboard(board) {
+
+            return Self::find_and_apply_next_valid_move_using_bitboard(board, player, (start_row, start_col));
+        }
+
+        let move_kind = self.find_move_kind(board, player);
 
         let mut row = start_row;
         let mut col = start_col;
-        while row < Board::SIZE {
-            while col < Board::SIZE {
+        while row < board.size() {
+            while col < board.size() {
 
-                if Self::find_flip_cells_for_move_internal(board, player, (row, col), &mut self.adjacent_opposites, &mut self.flip_cells) {
+                if Self::find_flip_cells_for_move_of_kind(move_kind, board, player, (row, col), &mut self.adjacent_opposites, &mut self.flip_cells) {
 
                     Self::apply_move(board, player, (row, col), &self.flip_cells);
-                    return (row, col);
+                    return Some((row, col));
 
                 }
                 col += 1;
@@ -122,13 +283,14 @@ impl Referee {
             col = 0;
         }
 
-        (Board::SIZE, Board::SIZE)
+        None
     }
 
     pub fn apply_move(board: &mut Board, player: Player, (row, col): Move, flip_cells: &CellList) {
 
         // Place the current player's piece
         board.grid[row][col] = Cell::Taken(player);
+        board.take_disc(player);
         
         // flip cells
         for (flip_row, flip_col) in flip_cells.iter() {
@@ -139,6 +301,136 @@ impl Referee {
 
     // internal
 
+    // under misère rules, empty squares would only count against the winner, so they're left out
+    // what's left after splitting them evenly goes to the first of the tied players, in the order of turns,
+    // so that the score adds up to the board, even though the game is still a tie
+    fn hand_out_empty_squares(board: &Board, winners: &[Player]) -> Score {
+
+        let mut discs = Self::count_disks_per_player(board);
+
+        if board.ruleset == Ruleset::Standard && !winners.is_empty() {
+
+            let empty_count = (0..board.size())
+                .flat_map(|row| (0..board.size()).map(move |col| (row, col)))
+                .filter(|&cell| board.cell(cell) == Cell::Empty)
+                .count();
+
+            for (i, &winner) in winners.iter().enumerate() {
+
+                let remainder = if i < empty_count % winners.len() { 1 } else { 0 };
+                discs[winner as usize] += (empty_count / winners.len() + remainder) as i32;
+            }
+        }
+
+        Score { discs, player_count: board.player_count(), ruleset: board.ruleset }
+    }
+
+    fn uses_bitboard(&self, board: &Board) -> bool {
+
+        self.representation == Representation::BitBoard && BitBoard::supports(board)
+    }
+
+    fn has_capturing_move(&mut self, board: &Board, player: Player) -> bool {
+
+        if let Some((cached_board, cached_player, has_capturing_move)) = &self.capturing_move
+            && *cached_player == player
+            && cached_board == board {
+
+            return *has_capturing_move;
+        }
+
+        let has_capturing_move = (0..board.size())
+            .flat_map(|row| (0..board.size()).map(move |col| (row, col)))
+            .any(|cell| Self::find_flip_cells_for_move_internal(board, player, cell, &mut self.adjacent_opposites, &mut self.flip_cells));
+
+        self.capturing_move = Some((board.clone(), player, has_capturing_move));
+
+        has_capturing_move
+    }
+
+    fn find_move_kind(&mut self, board: &Board, player: Player) -> MoveKind {
+
+        if !board.has_disc(player) {
+
+            MoveKind::None
+
+        } else if board.is_placement_phase() {
+
+            MoveKind::CentrePlacement
+
+        } else if board.is_rolit() && !self.has_capturing_move(board, player) {
+
+            MoveKind::Placement
+
+        } else {
+
+            MoveKind::Capture
+        }
+    }
+
+    fn find_flip_cells_for_move_of_kind(move_kind: MoveKind, board: &Board, player: Player, maybe_move: Move, adjacent_opposites: &mut CellList, result: &mut CellList) -> bool {
+
+        result.count = 0;
+
+        if !board.contains(maybe_move) {
+
+            return false;
+        }
+
+        match move_kind {
+            MoveKind::None => false,
+            MoveKind::CentrePlacement => board.cell(maybe_move) == Cell::Empty && board.centre().contains(&maybe_move),
+            MoveKind::Capture => Self::find_flip_cells_for_move_internal(board, player, maybe_move, adjacent_opposites, result),
+            MoveKind::Placement => Self::is_placement(board, maybe_move),
+        }
+    }
+
+    fn is_placement(board: &Board, maybe_move: Move) -> bool {
+
+        board.cell(maybe_move) == Cell::Empty
+            && DIRECTIONS.iter().filter_map(|&direction| board.neighbour(maybe_move, direction)).any(|other| matches!(board.cell(other), Cell::Taken(_)))
+    }
+
+    fn collect_cells(bits: u64, result: &mut CellList) -> bool {
+
+        result.count = 0;
+        for cell in bitboard::iter_cells(bits) {
+
+            result.push_back(cell);
+        }
+
+        result.count != 0
+    }
+
+    fn find_and_apply_next_valid_move_using_bitboard(board: &mut Board, player: Player, (start_row, start_col): Move) -> Option<Move> {
+
+        if start_row >= BitBoard::SIZE {
+
+            return None;
+        }
+
+        let bit_board = BitBoard::from_board(board);
+
+        // only the moves from the start cell onwards
+        let start_index = start_row * BitBoard::SIZE + start_col.min(BitBoard::SIZE);
+        let remaining_moves = match 1u64.checked_shl(start_index as u32) {
+            Some(start_bit) => bit_board.find_valid_moves(player) & !(start_bit - 1),
+            None => 0,
+        };
+
+        let next_move = bitboard::iter_cells(remaining_moves).next()?;
+
+        let (row, col) = next_move;
+        board.grid[row][col] = Cell::Taken(player);
+        board.take_disc(player);
+        for (flip_row, flip_col) in bitboard::iter_cells(bit_board.find_flips(player, next_move)) {
+
+            board.grid[flip_row][flip_col] = Cell::Taken(player);
+        }
+
+        Some(next_move)
+    }
+
     fn find_flip_cells_for_move_internal(board: &Board, player: Player, maybe_move: Move, adjacent_opposites: &mut CellList, flip_cells: &mut CellList) -> bool {
         
         match board.cell(maybe_move) {
@@ -154,46 +446,23 @@ impl Referee {
                     false
                 }
             },
-            Cell::Taken(_) => false
+            Cell::Taken(_) | Cell::Blocked => false
         }
     }
 
-    fn find_adjacent_opposites(board: &Board, player: Player, (row, col): Move, result: &mut CellList) -> bool {
-
-        let start_row = match row {
-            0 => 0,
-            current_row => current_row - 1
-        };
-        let end_row = match row + 1 {
-            Board::SIZE => Board::SIZE,
-            next_row => next_row + 1
-        };
-        let start_col = match col {
-            0 => 0,
-            current_col => current_col - 1
-        };
-        let end_col = match col + 1 {
-            Board::SIZE => Board::SIZE,
-            next_col => next_col + 1
-        };
+    fn find_adjacent_opposites(board: &Board, player: Player, maybe_move: Move, result: &mut CellList) -> bool {
 
         result.count = 0;
 
-        for other_row in start_row..end_row {
-            for other_col in start_col..end_col {
-
-                if other_row != row || other_col != col {
+        for direction in DIRECTIONS {
 
-                    if let Cell::Taken(other_disk) = board.grid[other_row][other_col] {
+            if let Some(other) = board.neighbour(maybe_move, direction)
+                && let Cell::Taken(other_disk) = board.cell(other)
+                && other_disk != player {
 
-                        if other_disk != player {
-                            
-                            result.push_back((other_row, other_col));
-                        }
-                    }
-                }
+                result.push_back(other);
             }
-        };
+        }
 
         result.count != 0
     }
@@ -203,9 +472,12 @@ impl Referee {
 
         result.count = 0;
 
+        // a neighbour across a wrapped edge is a whole board away
+        let unwrap = |step: i32| step.signum() * if step.abs() > 1 { -1 } else { 1 };
+
         for (adjacent_row, adjacent_col) in adjacent_opposites.iter() {
 
-            let direction = (adjacent_row as i32 - row as i32, adjacent_col  as i32 - col as i32);
+            let direction = (unwrap(adjacent_row as i32 - row as i32), unwrap(adjacent_col as i32 - col as i32));
             Self::cast_ray_recursive(board, player, (adjacent_row, adjacent_col), direction, result);
         }
 
@@ -214,7 +486,8 @@ impl Referee {
 
     // cast a ray in the checked direction
     // the ray is successful if a cell belonging to the player is found
-    // then all disks in between should be flipped
+    // then all disks in between should be flipped, but not the player's disk closing the line
+    // on a torus, a ray without the player's disk ends up back at the empty cell of the move
     //
     // this problem lends itself to a recursive approach, but recursion can be inefficient,
     // so at some point we might want to try an iterative approach and see if that helps performance,
@@ -223,31 +496,22 @@ impl Referee {
         
         match board.grid[row][col] {
 
-            Cell::Empty => false,
-            Cell::Taken(color) if color == player => {
-                
-                result.push_back((row, col));
-                true
-            },
+            Cell::Empty | Cell::Blocked => false,
+            Cell::Taken(color) if color == player => true,
             Cell::Taken(_) => {
 
-                let new_row = row as i32 + row_direction;
-                let new_col = col as i32 + col_direction;
-                if new_row < 0 || new_row >= Board::SIZE as i32 || new_col < 0 || new_col >= Board::SIZE as i32 {
-
-                    false
+                match board.neighbour((row, col), (row_direction, col_direction)) {
 
-                } else {
+                    Some(next) if Self::cast_ray_recursive(board, player, next, (row_direction, col_direction), result) => {
 
-                    if Self::cast_ray_recursive(board, player, (new_row as usize, new_col as usize), (row_direction, col_direction), result) {
+                        // both diagonals of a torus cross again halfway around, so a disk may already be in the list
+                        if board.topology == Topology::Flat || !result.iter().any(|cell| cell == (row, col)) {
 
-                        result.push_back((row, col));
+                            result.push_back((row, col));
+                        }
                         true
-
-                    } else {
-
-                        false
                     }
+                    _ => false,
                 }
             }
         }
@@ -0,0 +1,102 @@
+use rand::Rng;
+
+use crate::board::Action;
+use crate::board::Board;
+use crate::board::Player;
+use crate::common::CellList;
+use crate::referee::Outcome;
+use crate::referee::Referee;
+use crate::strategy::Parameter;
+use crate::strategy::RequestKind;
+use crate::strategy::SearchContext;
+use crate::strategy::Strategy;
+
+// a strategy with its configuration, taking part in a headless game
+pub struct Contestant<'a> {
+
+    pub strategy: &'a mut dyn Strategy,
+    pub parameters: &'a [Parameter],
+}
+
+// plays a whole game without GUI or agent thread, black contestant first
+// the first opening_move_count moves are random, so that games between deterministic contestants differ
+// a contestant that fails to come up with a valid move forfeits the game
+pub fn play_game(search: &mut SearchContext, contestants: &mut [Contestant; 2], opening_move_count: usize) -> Outcome {
+
+    let mut board = Board::default();
+    let mut player = Player::Black;
+    let mut flip_cells = CellList::default();
+    let mut valid_moves = CellList::default();
+    let mut move_count = 0;
+
+    loop {
+
+        if !search.referee.find_all_valid_moves(&board, player, &mut valid_moves) {
+
+            // pass, or end the game if neither player can move
+            player = player.opponent();
+            if !search.referee.find_all_valid_moves(&board, player, &mut valid_moves) {
+
+                return Referee::check_outcome(&board);
+            }
+        }
+
+        let action = if move_count < opening_move_count {
+
+            Action::Place(valid_moves.get(search.rng.random_range(..valid_moves.count)))
+
+        } else {
+
+            // a new request for every move, so that the node count starts over
+            search.begin_request(RequestKind::Move, move_count as u64 + 1, None);
+            let contestant = &mut contestants[player as usize];
+            let action = contestant.strategy.choose_move(search, &board, player, contestant.parameters);
+            search.end_request();
+            action
+        };
+
+        // the player has a valid move, so passing is against the rules too
+        let Action::Place(next_move) = action else {
+
+            return Referee::forfeit(&board, player.opponent());
+        };
+
+        if !search.referee.find_flip_cells_for_move(&board, player, next_move, &mut flip_cells) {
+
+            return Referee::forfeit(&board, player.opponent());
+        }
+
+        Referee::apply_move(&mut board, player, next_move, &flip_cells);
+        player = player.opponent();
+        move_count += 1;
+    }
+}
+
+// plays game_count games, alternating colors, and returns the score of the first contestant
+// a won game is worth 1 and a tie 0.5, so the score is between 0 and game_count
+pub fn play_match<'a>(search: &mut SearchContext, first: Contestant<'a>, second: Contestant<'a>, game_count: usize, opening_move_count: usize) -> f64 {
+
+    let mut contestants = [first, second];
+    let mut score = 0.0;
+
+    for game in 0..game_count {
+
+        // the first contestant plays black in even games
+        let first_player = if game % 2 == 0 { Player::Black } else { Player::White };
+        if first_player == Player::White {
+            contestants.swap(0, 1);
+        }
+
+        score += match play_game(search, &mut contestants, opening_move_count) {
+            Outcome::Won(player, _) if player == first_player => 1.0,
+            Outcome::Won(..) => 0.0,
+            Outcome::Tie(_) => 0.5,
+        };
+
+        if first_player == Player::White {
+            contestants.swap(0, 1);
+        }
+    }
+
+    score
+}
@@ -1,11 +1,13 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{collections::HashMap, fmt::Display, time::Duration};
 
-use crate::{board::Player, referee::Outcome};
+use crate::{agent::SearchStatistics, board::Player, referee::Outcome};
 
 pub struct Statistic {
     win_ratio: f64,
     tie_ratio: f64,
     lose_ratio: f64,
+    // the average final score of the player minus that of the best opponent
+    margin: f64,
     count: f64,
 }
 impl Default for Statistic {
@@ -16,6 +18,7 @@ impl Default for Statistic {
             win_ratio: 0.0,
             tie_ratio: 0.0,
             lose_ratio: 0.0,
+            margin: 0.0,
             count: 0.0,
         }   
     }
@@ -25,34 +28,65 @@ impl Display for Statistic {
 
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         
-        write!(f, "{:.1}%, {:.1}%, {:.1}%, ({:.0})", self.win_ratio * 100.0, self.tie_ratio * 100.0, self.lose_ratio * 100.0, self.count)
+        write!(f, "{:.1}%, {:.1}%, {:.1}%, {:+.1}, ({:.0})", self.win_ratio * 100.0, self.tie_ratio * 100.0, self.lose_ratio * 100.0, self.margin, self.count)
     }
 }
 
-pub struct Statistics {
+// search statistics summed up over many moves
+#[derive(Default, Clone)]
+pub struct SearchStatistic {
+    move_count: u64,
+    node_count: u64,
+    duration: Duration,
+    depth_sum: u64,
+}
 
-    pub data: HashMap<String, Statistic>,
+impl SearchStatistic {
 
+    pub fn add(&mut self, statistics: &SearchStatistics) {
+
+        self.move_count += 1;
+        self.node_count += statistics.node_count;
+        self.duration += statistics.duration;
+        self.depth_sum += statistics.depth as u64;
+    }
 }
 
-impl Default for Statistics {
+impl Display for SearchStatistic {
 
-    fn default() -> Self {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 
-        Statistics {
-            data: HashMap::new(),
-        }    
+        let moves = self.move_count.max(1) as f64;
+        let seconds = self.duration.as_secs_f64();
+        let nodes_per_second = if seconds > 0.0 { self.node_count as f64 / seconds } else { 0.0 };
+
+        write!(f, "{} moves, depth {:.1}, {:.0} nodes/move, {:.3} s/move, {:.0} nodes/s",
+            self.move_count, self.depth_sum as f64 / moves, self.node_count as f64 / moves, seconds / moves, nodes_per_second)
     }
 }
 
+#[derive(Default)]
+pub struct Statistics {
+
+    pub data: HashMap<String, Statistic>,
+
+    // per player name
+    pub search_data: HashMap<String, SearchStatistic>,
+}
+
 impl Statistics {
+
+    pub fn add_search_datum(&mut self, name: String, statistics: &SearchStatistics) {
+
+        self.search_data.entry(name).or_default().add(statistics);
+    }
     
     pub fn add_datum(&mut self, name: String, player: Player, outcome: &Outcome) {
         
-        let statistic = self.data.entry(name).or_insert(Statistic::default());
+        let statistic = self.data.entry(name).or_default();
         let (win_value, tie_value, lose_value) = match *outcome {
 
-            Outcome::Won(winning_player) => {
+            Outcome::Won(winning_player, _) => {
                 if player == winning_player {
 
                     (1.0, 0.0, 0.0)
@@ -62,13 +96,15 @@ impl Statistics {
                     (0.0, 0.0, 1.0)
                 }
             },
-            Outcome::Tie => (0.0, 1.0, 0.0)
+            Outcome::Tie(_) => (0.0, 1.0, 0.0)
         };
+        let margin = outcome.score().margin(player) as f64;
         
         let mut ratios = [
             (&mut statistic.win_ratio, win_value),
             (&mut statistic.tie_ratio, tie_value),
             (&mut statistic.lose_ratio, lose_value),
+            (&mut statistic.margin, margin),
         ];
         
         for (ratio, value) in ratios.iter_mut() {
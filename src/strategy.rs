@@ -0,0 +1,331 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::time::Duration;
+use std::time::Instant;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::common::CellList;
+use crate::board::Action;
+use crate::board::Player;
+use crate::board::Board;
+use crate::referee::Referee;
+use crate::agent::AgentResponse;
+use crate::agent::AnalysisLine;
+use crate::agent::SearchStatistics;
+use crate::agent::ThinkingReport;
+use crate::baseline::CornerStrategy;
+use crate::baseline::GreedyStrategy;
+use crate::baseline::MobilityStrategy;
+use crate::baseline::PositionalStrategy;
+use crate::baseline::RandomStrategy;
+use crate::minimax::MinimaxStrategy;
+use crate::learning::LearnedStrategy;
+use crate::nboard::ExternalEngineStrategy;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParameterValue {
+    Integer { value: i32, min: i32, max: i32 },
+    Text(String),
+}
+
+// a setting of a strategy that can be tweaked from the GUI
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+
+    pub name: &'static str,
+    pub value: ParameterValue,
+}
+
+impl Parameter {
+
+    pub fn integer(name: &'static str, value: i32, min: i32, max: i32) -> Self {
+
+        Parameter { name, value: ParameterValue::Integer { value, min, max } }
+    }
+
+    pub fn text(name: &'static str, value: &str) -> Self {
+
+        Parameter { name, value: ParameterValue::Text(value.to_string()) }
+    }
+
+    // panics if the parameter isn't an integer, since strategies know the types of their own parameters
+    pub fn as_integer(&self) -> i32 {
+
+        match self.value {
+            ParameterValue::Integer { value, .. } => value,
+            ParameterValue::Text(_) => panic!("parameter {} is not an integer", self.name),
+        }
+    }
+
+    pub fn as_text(&self) -> &str {
+
+        match &self.value {
+            ParameterValue::Text(value) => value,
+            ParameterValue::Integer { .. } => panic!("parameter {} is not a text", self.name),
+        }
+    }
+}
+
+impl std::fmt::Display for Parameter {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+
+        match &self.value {
+            ParameterValue::Integer { value, .. } => write!(f, "{} {}", self.name, value),
+            ParameterValue::Text(value) => write!(f, "{} {}", self.name, value),
+        }
+    }
+}
+
+// a way of choosing moves
+// implement this and add it to the registry below, and it will show up in the GUI and the statistics
+pub trait Strategy: Send {
+
+    fn name(&self) -> &'static str;
+
+    // the configurable parameters of the strategy, with their default values
+    fn parameters(&self) -> Vec<Parameter> {
+
+        Vec::new()
+    }
+
+    // tells different configurations of the strategy apart, e.g. in the statistics
+    fn label(&self, parameters: &[Parameter]) -> String {
+
+        let mut label = self.name().to_string();
+        for parameter in parameters {
+            label += &format!(", {parameter}");
+        }
+        label
+    }
+
+    // passes if there is no valid move, and a strategy unable to come up with a move passes too,
+    // which is only valid if the player has to
+    // parameters are in the same order as returned by parameters()
+    fn choose_move(&mut self, search: &mut SearchContext, board: &Board, player: Player, parameters: &[Parameter]) -> Action;
+}
+
+// every available strategy, in the order they are presented
+pub fn registry() -> Vec<Box<dyn Strategy>> {
+
+    vec![
+        Box::new(RandomStrategy),
+        Box::new(GreedyStrategy),
+        Box::new(MobilityStrategy),
+        Box::new(CornerStrategy),
+        Box::new(PositionalStrategy),
+        Box::new(MinimaxStrategy),
+        Box::new(LearnedStrategy::default()),
+        Box::new(ExternalEngineStrategy::default()),
+    ]
+}
+
+const NO_REQUEST_ID: u64 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RequestKind {
+    Move,
+    Analysis,
+}
+
+// shared by the game and its agent, for stopping requests, in which case the best move found so far is returned
+// a move and an analysis can be in flight at the same time, so each kind is stopped on its own,
+// and only the one request with the stopped id is affected
+// request ids start at 1, since 0 stands for no request, which is never stopped
+#[derive(Default)]
+pub struct StopFlags {
+
+    // indexed by RequestKind
+    stopped_request_ids: [AtomicU64; 2],
+    // stops every request, for shutting down
+    stop_all: AtomicBool,
+}
+
+impl StopFlags {
+
+    pub fn stop(&self, kind: RequestKind, request_id: u64) {
+
+        self.stopped_request_ids[kind as usize].store(request_id, Ordering::Relaxed);
+    }
+
+    pub fn stop_all(&self) {
+
+        self.stop_all.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_stopped(&self, kind: RequestKind, request_id: u64) -> bool {
+
+        request_id != NO_REQUEST_ID
+            && (self.stop_all.load(Ordering::Relaxed) || self.stopped_request_ids[kind as usize].load(Ordering::Relaxed) == request_id)
+    }
+}
+
+// the finalizer of splitmix64, which scatters nearby inputs all over the range
+fn mix(value: u64) -> u64 {
+
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// the seed of the agent decisions at a position of a game, derived from the seed of the game
+// mixed rather than added, so that nearby games don't share the decisions of their positions
+pub fn position_seed(game_seed: u64, move_count: u64) -> u64 {
+
+    mix(mix(game_seed) ^ move_count.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+// the resources and bookkeeping shared by all strategies of an agent
+// not thread-safe, every thread needs its own SearchContext
+pub struct SearchContext {
+
+    // all randomness of the strategies must come from here, for the sake of reproducibility
+    pub rng: StdRng,
+    pub referee: Referee,
+    pub valid_moves: CellList,
+    response_sender: mpsc::Sender<AgentResponse>,
+    stop_flags: Arc<StopFlags>,
+    current_request_kind: RequestKind,
+    current_request_id: u64,
+    node_count: u64,
+    progress: SearchProgress,
+    // only while searching for a move, as opposed to analyzing
+    report_progress: bool,
+}
+
+struct SearchProgress {
+
+    start: Instant,
+    last_report: Instant,
+    // the depth currently being searched
+    depth: usize,
+    // the depth of the best line, zero if the strategy doesn't report one
+    completed_depth: usize,
+    best_line: Option<AnalysisLine>,
+}
+
+impl SearchProgress {
+
+    fn new() -> Self {
+
+        let start = Instant::now();
+        SearchProgress { start, last_report: start, depth: 1, completed_depth: 0, best_line: None }
+    }
+}
+
+impl SearchContext {
+
+    pub fn new(response_sender: mpsc::Sender<AgentResponse>, stop_flags: Arc<StopFlags>, seed: u64) -> Self {
+
+        SearchContext {
+            rng: StdRng::seed_from_u64(seed),
+            referee: Referee::default(),
+            valid_moves: CellList::default(),
+            response_sender,
+            stop_flags,
+            current_request_kind: RequestKind::Move,
+            current_request_id: NO_REQUEST_ID,
+            node_count: 0,
+            progress: SearchProgress::new(),
+            report_progress: false,
+        }
+    }
+
+    // for running strategies outside of an agent, e.g. in tools and tests
+    // it's never stopped, and can be used right away without beginning a request
+    pub fn headless(seed: u64) -> Self {
+
+        let (response_sender, _response_receiver) = mpsc::channel();
+        Self::new(response_sender, Arc::new(StopFlags::default()), seed)
+    }
+
+    // thinking reports are only sent for move requests
+    // a seed makes the request reproducible, regardless of what was requested before it
+    pub fn begin_request(&mut self, kind: RequestKind, request_id: u64, seed: Option<u64>) {
+
+        self.current_request_kind = kind;
+        self.current_request_id = request_id;
+        if let Some(seed) = seed {
+            self.rng = StdRng::seed_from_u64(seed);
+        }
+        self.node_count = 0;
+        self.progress = SearchProgress::new();
+        self.report_progress = kind == RequestKind::Move;
+    }
+
+    pub fn end_request(&mut self) {
+
+        self.report_progress = false;
+    }
+
+    // the statistics of the current request so far
+    pub fn statistics(&self) -> SearchStatistics {
+
+        let best_line = self.progress.best_line.as_ref();
+
+        SearchStatistics {
+            node_count: self.node_count,
+            duration: self.progress.start.elapsed(),
+            depth: self.progress.completed_depth,
+            score: best_line.map(|line| line.score),
+            principal_variation: best_line.map(|line| line.principal_variation.clone()).unwrap_or_default(),
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+
+        self.stop_flags.is_stopped(self.current_request_kind, self.current_request_id)
+    }
+
+    // called for every searched node, so it needs to be cheap most of the time
+    pub fn count_node(&mut self) {
+
+        self.node_count += 1;
+
+        const REPORT_CHECK_INTERVAL: u64 = 1024;
+        const REPORT_INTERVAL: Duration = Duration::from_millis(100);
+
+        if self.node_count.is_multiple_of(REPORT_CHECK_INTERVAL)
+            && self.report_progress
+            && self.progress.last_report.elapsed() >= REPORT_INTERVAL {
+
+            self.send_thinking_report();
+        }
+    }
+
+    pub fn report_depth(&mut self, depth: usize) {
+
+        self.progress.depth = depth;
+    }
+
+    // the best line found by a completed search of the current depth
+    pub fn report_best_line(&mut self, best_line: AnalysisLine) {
+
+        self.progress.completed_depth = self.progress.depth;
+        self.progress.best_line = Some(best_line);
+        self.send_thinking_report();
+    }
+
+    fn send_thinking_report(&mut self) {
+
+        if self.report_progress {
+
+            self.progress.last_report = Instant::now();
+            let elapsed_seconds = self.progress.start.elapsed().as_secs_f64();
+            let nodes_per_second = if elapsed_seconds > 0.0 { self.node_count as f64 / elapsed_seconds } else { 0.0 };
+
+            let _ = self.response_sender.send(AgentResponse::Thinking(Box::new(ThinkingReport {
+                request_id: self.current_request_id,
+                depth: self.progress.depth,
+                node_count: self.node_count,
+                nodes_per_second,
+                best_line: self.progress.best_line.clone(),
+            })));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::board::Action;
+use crate::board::Board;
+use crate::board::Cell;
+
+type Move = (usize, usize);
+
+// The eight symmetries of a square board, under which every position plays out the same
+// an opening book or a transposition table only has to know one position of every eight,
+// the canonical one, as long as the moves are mapped back to the board at hand
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    Identity,
+    // clockwise
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    // left to right
+    FlipHorizontal,
+    // top to bottom
+    FlipVertical,
+    // along the diagonal from a1, which swaps rows and columns
+    FlipDiagonal,
+    // along the other diagonal
+    FlipAntiDiagonal,
+}
+
+impl Symmetry {
+
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::FlipDiagonal,
+        Symmetry::FlipAntiDiagonal,
+    ];
+
+    // the symmetry that undoes this one
+    pub fn inverse(&self) -> Symmetry {
+
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            // every other one is its own inverse
+            symmetry => *symmetry,
+        }
+    }
+
+    pub fn apply_to_move(&self, board_size: usize, (row, col): Move) -> Move {
+
+        let last = board_size - 1;
+
+        match self {
+            Symmetry::Identity => (row, col),
+            Symmetry::Rotate90 => (col, last - row),
+            Symmetry::Rotate180 => (last - row, last - col),
+            Symmetry::Rotate270 => (last - col, row),
+            Symmetry::FlipHorizontal => (row, last - col),
+            Symmetry::FlipVertical => (last - row, col),
+            Symmetry::FlipDiagonal => (col, row),
+            Symmetry::FlipAntiDiagonal => (last - col, last - row),
+        }
+    }
+
+    pub fn apply_to_action(&self, board_size: usize, action: Action) -> Action {
+
+        match action {
+            Action::Place(next_move) => Action::Place(self.apply_to_move(board_size, next_move)),
+            Action::Pass => Action::Pass,
+        }
+    }
+
+    // everything but the cells stays the same, the rules, the players and the supplies of discs
+    pub fn apply(&self, board: &Board) -> Board {
+
+        let mut transformed = board.clone();
+
+        for row in 0..board.size() {
+            for col in 0..board.size() {
+
+                let (new_row, new_col) = self.apply_to_move(board.size(), (row, col));
+                transformed.grid[new_row][new_col] = board.grid[row][col];
+            }
+        }
+
+        transformed
+    }
+}
+
+// orders the boards, row by row, for picking the canonical one
+fn cell_key(cell: Cell) -> usize {
+
+    match cell {
+        Cell::Empty => 0,
+        Cell::Blocked => 1,
+        Cell::Taken(player) => 2 + player as usize,
+    }
+}
+
+fn board_key(board: &Board) -> Vec<usize> {
+
+    (0..board.size()).flat_map(|row| (0..board.size()).map(move |col| cell_key(board.grid[row][col]))).collect()
+}
+
+// the least of the eight transformed boards, along with the symmetry that transforms the board into it
+// when several symmetries lead to the same board, the first in the order of Symmetry::ALL is returned
+pub fn canonical(board: &Board) -> (Board, Symmetry) {
+
+    Symmetry::ALL.iter()
+        .map(|symmetry| (symmetry.apply(board), *symmetry))
+        .min_by_key(|(transformed, _)| board_key(transformed))
+        .unwrap()
+}
+
+// maps a move on the canonical board back to the board that was made canonical by the symmetry
+pub fn from_canonical(symmetry: Symmetry, board_size: usize, canonical_move: Move) -> Move {
+
+    symmetry.inverse().apply_to_move(board_size, canonical_move)
+}
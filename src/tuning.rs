@@ -0,0 +1,176 @@
+use std::thread;
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::selfplay;
+use crate::selfplay::Contestant;
+use crate::strategy;
+use crate::strategy::Parameter;
+use crate::strategy::ParameterValue;
+use crate::strategy::SearchContext;
+
+// Optimizes the parameters of a strategy with a genetic algorithm
+// the fitness of a candidate is its score in a match against the base parameters
+
+pub struct TuningConfig {
+
+    // an index into the strategy registry
+    pub strategy: usize,
+    // the starting point of the optimization, as well as the opponent of every candidate
+    pub base_parameters: Vec<Parameter>,
+    // indices into base_parameters, only integer parameters can be tuned
+    pub tuned_parameters: Vec<usize>,
+    pub population_size: usize,
+    pub generation_count: usize,
+    // per candidate and generation
+    pub games_per_match: usize,
+    // random moves at the start of every game, for variety
+    pub opening_move_count: usize,
+    // the probability of every tuned parameter to mutate in a new candidate
+    pub mutation_rate: f64,
+    pub seed: u64,
+}
+
+impl Default for TuningConfig {
+
+    fn default() -> Self {
+
+        TuningConfig {
+            strategy: 0,
+            base_parameters: Vec::new(),
+            tuned_parameters: Vec::new(),
+            population_size: 12,
+            generation_count: 10,
+            games_per_match: 10,
+            opening_move_count: 4,
+            mutation_rate: 0.3,
+            seed: 0,
+        }
+    }
+}
+
+pub struct TuningResult {
+
+    pub parameters: Vec<Parameter>,
+    // measured against the base parameters, in a final match longer than the ones during tuning
+    pub win_rate: f64,
+}
+
+// how many of the best candidates survive unchanged into the next generation
+const ELITE_COUNT: usize = 2;
+const TOURNAMENT_SIZE: usize = 3;
+const FINAL_MATCH_FACTOR: usize = 4;
+
+// report is called after every generation with (generation, best win rate, best parameters)
+pub fn tune(config: &TuningConfig, mut report: impl FnMut(usize, f64, &[Parameter])) -> TuningResult {
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+
+    let mut population = vec![config.base_parameters.clone()];
+    while population.len() < config.population_size.max(1) {
+
+        population.push(mutate(config, &config.base_parameters, 1.0, &mut rng));
+    }
+
+    for generation in 0..config.generation_count {
+
+        let win_rates = evaluate_population(config, &population, config.games_per_match, generation as u64);
+
+        let mut ranking: Vec<usize> = (0..population.len()).collect();
+        ranking.sort_by(|&a, &b| win_rates[b].total_cmp(&win_rates[a]));
+
+        report(generation, win_rates[ranking[0]], &population[ranking[0]]);
+
+        if generation + 1 == config.generation_count {
+
+            population = vec![population[ranking[0]].clone()];
+            break;
+        }
+
+        let mut next_population: Vec<Vec<Parameter>> = ranking.iter().take(ELITE_COUNT).map(|&i| population[i].clone()).collect();
+        while next_population.len() < population.len() {
+
+            let mother = select(&win_rates, &mut rng);
+            let father = select(&win_rates, &mut rng);
+            let child = crossover(config, &population[mother], &population[father], &mut rng);
+            next_population.push(mutate(config, &child, config.mutation_rate, &mut rng));
+        }
+
+        population = next_population;
+    }
+
+    let parameters = population.swap_remove(0);
+    let win_rates = evaluate_population(config, std::slice::from_ref(&parameters), config.games_per_match * FINAL_MATCH_FACTOR, config.generation_count as u64);
+
+    TuningResult { parameters, win_rate: win_rates[0] }
+}
+
+// plays a match for every candidate, in parallel, and returns their win rates
+fn evaluate_population(config: &TuningConfig, population: &[Vec<Parameter>], game_count: usize, generation: u64) -> Vec<f64> {
+
+    thread::scope(|scope| {
+
+        let handles: Vec<_> = population.iter().enumerate().map(|(i, candidate)| {
+
+            scope.spawn(move || {
+
+                // every thread needs its own strategies and search context
+                let mut candidate_strategies = strategy::registry();
+                let mut base_strategies = strategy::registry();
+                let mut search = SearchContext::headless(config.seed ^ (generation << 32) ^ i as u64);
+
+                let score = selfplay::play_match(
+                    &mut search,
+                    Contestant { strategy: candidate_strategies[config.strategy].as_mut(), parameters: candidate },
+                    Contestant { strategy: base_strategies[config.strategy].as_mut(), parameters: &config.base_parameters },
+                    game_count,
+                    config.opening_move_count,
+                );
+
+                score / game_count.max(1) as f64
+            })
+        }).collect();
+
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+// tournament selection, returns an index into the population
+fn select(win_rates: &[f64], rng: &mut StdRng) -> usize {
+
+    (0..TOURNAMENT_SIZE)
+        .map(|_| rng.random_range(..win_rates.len()))
+        .max_by(|&a, &b| win_rates[a].total_cmp(&win_rates[b]))
+        .unwrap()
+}
+
+// uniform crossover, every tuned parameter is taken from either parent
+fn crossover(config: &TuningConfig, mother: &[Parameter], father: &[Parameter], rng: &mut StdRng) -> Vec<Parameter> {
+
+    let mut child = mother.to_vec();
+    for &i in &config.tuned_parameters {
+
+        if rng.random_bool(0.5) {
+            child[i] = father[i].clone();
+        }
+    }
+    child
+}
+
+// nudges every tuned parameter with the given probability, by up to a tenth of its range
+fn mutate(config: &TuningConfig, parameters: &[Parameter], mutation_rate: f64, rng: &mut StdRng) -> Vec<Parameter> {
+
+    let mut mutant = parameters.to_vec();
+    for &i in &config.tuned_parameters {
+
+        if let ParameterValue::Integer { value, min, max } = &mut mutant[i].value
+            && rng.random_bool(mutation_rate) {
+
+            let step = ((*max - *min) / 10).max(1);
+            *value = (*value + rng.random_range(-step..=step)).clamp(*min, *max);
+        }
+    }
+    mutant
+}
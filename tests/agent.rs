@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+
+use othello::agent::Agent;
+use othello::agent::AgentRequest;
+use othello::agent::AgentResponse;
+use othello::agent::AnalysisRequest;
+use othello::agent::AnalysisResult;
+use othello::agent::MoveRequest;
+use othello::agent::MoveResult;
+use othello::baseline::RandomStrategy;
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::record::GameRecord;
+use othello::record::Turn;
+use othello::referee::Referee;
+use othello::strategy;
+use othello::strategy::Parameter;
+use othello::strategy::RequestKind;
+use othello::strategy::SearchContext;
+use othello::strategy::StopFlags;
+use othello::strategy::Strategy;
+
+fn move_request(request_id: u64, depth: i32) -> MoveRequest {
+
+    let strategy = strategy::registry().iter().position(|strategy| strategy.name() == "Minimax").unwrap();
+    let mut parameters = strategy::registry()[strategy].parameters();
+    parameters[0] = Parameter::integer("Depth", depth, 1, 10);
+
+    MoveRequest { request_id, board: Board::default(), player: Player::Black, pace_response: false, strategy, parameters, seed: Some(0) }
+}
+
+fn analysis_request(request_id: u64, recursion_depth: usize) -> AnalysisRequest {
+
+    AnalysisRequest { request_id, board: Board::default(), player: Player::Black, recursion_depth, line_count: 4, seed: Some(0) }
+}
+
+// sends both requests at once, stops one of them right away, and waits for both results
+fn run_requests(move_request: MoveRequest, analysis_request: AnalysisRequest, stopped: (RequestKind, u64)) -> (MoveResult, AnalysisResult) {
+
+    let (request_sender, request_receiver) = mpsc::channel();
+    let (response_sender, response_receiver) = mpsc::channel();
+    let stop_flags = Arc::new(StopFlags::default());
+    let agent_stop_flags = stop_flags.clone();
+
+    let agent_thread = thread::spawn(move || Agent::new(request_receiver, response_sender, agent_stop_flags, 0).run());
+
+    request_sender.send(AgentRequest::Move(move_request)).unwrap();
+    request_sender.send(AgentRequest::Analysis(analysis_request)).unwrap();
+    stop_flags.stop(stopped.0, stopped.1);
+    drop(request_sender);
+
+    let mut move_result = None;
+    let mut analysis_result = None;
+    for response in response_receiver.iter() {
+
+        match response {
+            AgentResponse::Move(result) => move_result = Some(*result),
+            AgentResponse::Analysis(result) => analysis_result = Some(*result),
+            AgentResponse::Thinking(_) => {}
+        }
+    }
+    agent_thread.join().unwrap();
+
+    (move_result.unwrap(), analysis_result.unwrap())
+}
+
+#[test]
+fn stopping_an_analysis_leaves_the_move_alone() {
+
+    let (move_result, analysis_result) = run_requests(move_request(1, 4), analysis_request(2, 1), (RequestKind::Analysis, 2));
+
+    assert_eq!(move_result.statistics.depth, 4);
+    // stopped before a single line was complete
+    assert!(analysis_result.lines.is_empty());
+}
+
+#[test]
+fn stopping_a_move_leaves_the_analysis_alone() {
+
+    let (move_result, analysis_result) = run_requests(move_request(1, 10), analysis_request(2, 2), (RequestKind::Move, 1));
+
+    assert!(move_result.statistics.depth < 10);
+    assert_eq!(analysis_result.lines.len(), 4);
+}
+
+#[test]
+fn a_fresh_context_is_not_stopped() {
+
+    let mut search = SearchContext::headless(0);
+    assert!(!search.is_stopped());
+
+    search.begin_request(RequestKind::Move, 1, None);
+    assert!(!search.is_stopped());
+}
+
+// plays a game of random moves the way the GUI seeds them, every position with a seed of its own
+fn play_seeded_game(game_seed: u64) -> GameRecord {
+
+    let mut search = SearchContext::headless(0);
+    let mut referee = Referee::default();
+    let mut record = GameRecord::new(Board::default(), Player::Black, game_seed);
+    let mut actions = Vec::new();
+
+    loop {
+
+        let (board, player) = record.replay(&mut referee);
+        if !referee.find_all_valid_actions(&board, player, &mut actions) {
+
+            return record;
+        }
+        if actions == [Action::Pass] {
+
+            record.push(Turn { player, action: Action::Pass });
+            continue;
+        }
+
+        let request_id = record.turns().len() as u64 + 1;
+        search.begin_request(RequestKind::Move, request_id, Some(strategy::position_seed(game_seed, record.move_count() as u64)));
+        let action = RandomStrategy.choose_move(&mut search, &board, player, &[]);
+        record.push(Turn { player, action });
+    }
+}
+
+#[test]
+fn games_with_the_same_seed_play_out_the_same() {
+
+    assert_eq!(play_seeded_game(31).turns(), play_seeded_game(31).turns());
+    assert_ne!(play_seeded_game(31).turns(), play_seeded_game(32).turns());
+
+    // the next game doesn't just replay this one a move later
+    for move_count in 0..60 {
+
+        assert_ne!(strategy::position_seed(31, move_count + 1), strategy::position_seed(32, move_count));
+    }
+}
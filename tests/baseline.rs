@@ -0,0 +1,65 @@
+use othello::baseline::CornerStrategy;
+use othello::baseline::GreedyStrategy;
+use othello::baseline::MobilityStrategy;
+use othello::baseline::PositionalStrategy;
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::board::Ruleset;
+use othello::strategy::SearchContext;
+use othello::strategy::Strategy;
+
+// black can either play e1, flipping three discs on the top edge,
+// or take the a8 corner, flipping one disc and leaving white without a move
+fn position(ruleset: Ruleset) -> Board {
+
+    let mut board = Board::from_text("
+        XOOO----
+        --------
+        --------
+        --------
+        --------
+        --------
+        --------
+        -OX-----
+    ").unwrap();
+    board.ruleset = ruleset;
+
+    board
+}
+
+const EDGE_MOVE: Action = Action::Place((0, 4));
+const CORNER_MOVE: Action = Action::Place((7, 0));
+
+fn choose_move(strategy: &mut dyn Strategy, ruleset: Ruleset) -> Action {
+
+    strategy.choose_move(&mut SearchContext::headless(33), &position(ruleset), Player::Black, &[])
+}
+
+#[test]
+fn greedy_flips_the_most_discs() {
+
+    assert_eq!(choose_move(&mut GreedyStrategy, Ruleset::Standard), EDGE_MOVE);
+    assert_eq!(choose_move(&mut GreedyStrategy, Ruleset::Misere), CORNER_MOVE);
+}
+
+#[test]
+fn min_mobility_leaves_the_opponent_without_moves() {
+
+    assert_eq!(choose_move(&mut MobilityStrategy, Ruleset::Standard), CORNER_MOVE);
+    assert_eq!(choose_move(&mut MobilityStrategy, Ruleset::Misere), CORNER_MOVE);
+}
+
+#[test]
+fn corners_takes_the_corner() {
+
+    assert_eq!(choose_move(&mut CornerStrategy, Ruleset::Standard), CORNER_MOVE);
+    assert_eq!(choose_move(&mut CornerStrategy, Ruleset::Misere), EDGE_MOVE);
+}
+
+#[test]
+fn positional_goes_by_the_table() {
+
+    assert_eq!(choose_move(&mut PositionalStrategy, Ruleset::Standard), CORNER_MOVE);
+    assert_eq!(choose_move(&mut PositionalStrategy, Ruleset::Misere), EDGE_MOVE);
+}
@@ -0,0 +1,95 @@
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use othello::board::Board;
+use othello::board::Cell;
+use othello::board::Player;
+use othello::common::CellList;
+use othello::referee::Referee;
+use othello::referee::Representation;
+
+mod common;
+
+fn sorted(cells: &CellList) -> Vec<(usize, usize)> {
+
+    let mut cells: Vec<_> = cells.iter().collect();
+    cells.sort();
+    cells
+}
+
+// every referee operation must give the same answer for both representations
+fn assert_representations_agree(bit_board_referee: &mut Referee, grid_referee: &mut Referee, board: &Board, player: Player) {
+
+    let mut bit_board_cells = CellList::default();
+    let mut grid_cells = CellList::default();
+
+    let has_moves = bit_board_referee.find_all_valid_moves(board, player, &mut bit_board_cells);
+    assert_eq!(has_moves, grid_referee.find_all_valid_moves(board, player, &mut grid_cells));
+    assert_eq!(bit_board_cells.iter().collect::<Vec<_>>(), grid_cells.iter().collect::<Vec<_>>());
+
+    for row in 0..board.size() {
+        for col in 0..board.size() {
+
+            let is_valid = bit_board_referee.validate_move(board, player, (row, col));
+            assert_eq!(is_valid, grid_referee.validate_move(board, player, (row, col)), "validity of {:?}", (row, col));
+
+            assert_eq!(is_valid, bit_board_referee.find_flip_cells_for_move(board, player, (row, col), &mut bit_board_cells));
+            assert_eq!(is_valid, grid_referee.find_flip_cells_for_move(board, player, (row, col), &mut grid_cells));
+            if is_valid {
+                assert_eq!(sorted(&bit_board_cells), sorted(&grid_cells), "flips of {:?}", (row, col));
+            }
+        }
+    }
+
+    // walking through the moves one by one, the way the minimax search does
+    let mut start = (0, 0);
+    loop {
+
+        let mut bit_board_board = board.clone();
+        let mut grid_board = board.clone();
+
+        let bit_board_move = bit_board_referee.find_and_apply_next_valid_move(&mut bit_board_board, player, start);
+        let grid_move = grid_referee.find_and_apply_next_valid_move(&mut grid_board, player, start);
+
+        assert_eq!(bit_board_move, grid_move);
+        assert_eq!(bit_board_board.grid, grid_board.grid);
+
+        let Some((row, col)) = bit_board_move else {
+            break;
+        };
+        start = (row, col + 1);
+    }
+}
+
+#[test]
+fn bitboard_and_grid_agree_on_random_games() {
+
+    let mut rng = StdRng::seed_from_u64(36);
+    let mut bit_board_referee = Referee::with_representation(Representation::BitBoard);
+    let mut grid_referee = Referee::with_representation(Representation::Grid);
+
+    for game in 0..100 {
+
+        let mut board = Board::default();
+
+        // every other game has holes in the board
+        if game % 2 == 1 {
+
+            for _ in 0..6 {
+
+                let (row, col) = (rng.random_range(..board.size()), rng.random_range(..board.size()));
+                if board.grid[row][col] == Cell::Empty {
+
+                    board.grid[row][col] = Cell::Blocked;
+                }
+            }
+        }
+
+        for (board, player, _action) in common::random_game(&board, Player::Black, game) {
+
+            assert_representations_agree(&mut bit_board_referee, &mut grid_referee, &board, player);
+            assert_representations_agree(&mut bit_board_referee, &mut grid_referee, &board, player.opponent());
+        }
+    }
+}
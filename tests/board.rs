@@ -0,0 +1,323 @@
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Cell;
+use othello::board::Player;
+use othello::board::Ruleset;
+use othello::board::Topology;
+use othello::common::CellList;
+use othello::evaluation;
+use othello::referee::Outcome;
+use othello::referee::Referee;
+use othello::statistics::Statistics;
+
+mod common;
+
+#[test]
+fn every_size_starts_with_four_centred_discs() {
+
+    let mut referee = Referee::default();
+    let mut valid_moves = CellList::default();
+
+    for size in (Board::MIN_SIZE..=Board::MAX_SIZE).step_by(2) {
+
+        let board = Board::new(size);
+        let centre = size / 2;
+
+        assert_eq!(board.size(), size);
+        assert_eq!(board.cell((centre - 1, centre - 1)), Cell::Taken(Player::White));
+        assert_eq!(board.cell((centre, centre)), Cell::Taken(Player::White));
+        assert_eq!(board.cell((centre - 1, centre)), Cell::Taken(Player::Black));
+        assert_eq!(board.cell((centre, centre - 1)), Cell::Taken(Player::Black));
+        assert_eq!(Referee::count_disks(&board, Player::Black), (2, 2));
+
+        // the same four openings as on the standard board, shifted to the centre
+        referee.find_all_valid_moves(&board, Player::Black, &mut valid_moves);
+        let expected = [(centre - 2, centre - 1), (centre - 1, centre - 2), (centre, centre + 1), (centre + 1, centre)];
+        assert_eq!(valid_moves.iter().collect::<Vec<_>>(), expected, "size {size}");
+    }
+}
+
+#[test]
+fn misere_rules_reverse_the_outcome_and_the_evaluation() {
+
+    let mut board = Board::from_text("
+        XXXX
+        XXOX
+        X---
+        ----").unwrap();
+
+    assert!(matches!(Referee::check_outcome(&board), Outcome::Won(Player::Black, _)));
+    assert!(evaluation::count_features(&board, Player::Black)[evaluation::DISC] > 0.0);
+
+    board.ruleset = Ruleset::Misere;
+    assert!(matches!(Referee::check_outcome(&board), Outcome::Won(Player::White, _)));
+    assert!(evaluation::count_features(&board, Player::Black)[evaluation::DISC] < 0.0);
+}
+
+#[test]
+fn empty_squares_go_to_the_winner() {
+
+    let board = Board::from_text("
+        XXXX
+        XXOX
+        X--#
+        ----").unwrap();
+
+    // the hole is no empty square
+    let outcome = Referee::check_outcome(&board);
+    assert_eq!(outcome.score().discs[..2], [14, 1]);
+    assert_eq!(outcome.score().to_string(), "14-1");
+    assert_eq!(outcome.score().margin(Player::White), -13);
+
+    let tied = Board::from_text("
+        XXOO
+        XXOO
+        ----
+        ----").unwrap();
+
+    assert!(matches!(Referee::check_outcome(&tied), Outcome::Tie(score) if score.discs[..2] == [8, 8]));
+
+    // an odd number of empty squares still adds up to the board
+    let tied = Board::from_text("
+        XXOO
+        XXOO
+        ---#
+        ----").unwrap();
+
+    assert!(matches!(Referee::check_outcome(&tied), Outcome::Tie(score) if score.discs[..2] == [8, 7]));
+
+    // under misère rules, the empty squares are left out, and the winner is ahead with fewer discs
+    let mut misere = board.clone();
+    misere.ruleset = Ruleset::Misere;
+    let outcome = Referee::check_outcome(&misere);
+    assert!(matches!(outcome, Outcome::Won(Player::White, _)));
+    assert_eq!(outcome.score().to_string(), "8-1");
+    assert_eq!(outcome.score().margin(Player::White), 7);
+    assert_eq!(outcome.score().margin(Player::Black), -7);
+
+    let mut statistics = Statistics::default();
+    statistics.add_datum("White".to_string(), Player::White, &outcome);
+    assert_eq!(statistics.data["White"].to_string(), "100.0%, 0.0%, 0.0%, +7.0, (1)");
+}
+
+#[test]
+fn blocked_cells_stop_lines_and_cannot_be_played() {
+
+    let mut referee = Referee::default();
+    let mut flip_cells = CellList::default();
+
+    let mut board = Board::from_text("
+        ----
+        XO#-
+        -O--
+        -X--").unwrap();
+
+    // the hole interrupts the line, which would be closed otherwise
+    assert!(!referee.find_flip_cells_for_move(&board, Player::Black, (1, 3), &mut flip_cells));
+    board.grid[1][2] = Cell::Taken(Player::White);
+    assert!(referee.find_flip_cells_for_move(&board, Player::Black, (1, 3), &mut flip_cells));
+    assert_eq!(flip_cells.iter().collect::<Vec<_>>(), [(1, 1), (1, 2)]);
+
+    // a hole can't be played, even if it would close a line
+    board.grid[0][1] = Cell::Blocked;
+    assert!(!referee.validate_move(&board, Player::Black, (0, 1)));
+    board.grid[0][1] = Cell::Empty;
+    assert!(referee.validate_move(&board, Player::Black, (0, 1)));
+
+    let mut octagon = Board::new(8);
+    octagon.cut_corners(2);
+    assert_eq!(octagon.cell((0, 0)), Cell::Blocked);
+    assert_eq!(octagon.cell((0, 1)), Cell::Blocked);
+    assert_eq!(octagon.cell((1, 1)), Cell::Empty);
+    assert_eq!(octagon.cell((0, 2)), Cell::Empty);
+}
+
+#[test]
+fn original_reversi_fills_the_centre_and_hands_over_discs() {
+
+    let mut referee = Referee::default();
+    let mut cells = CellList::default();
+    let mut board = Board::new_reversi(4);
+
+    // the centre is filled first, without capturing
+    let mut player = Player::Black;
+    for _ in 0..4 {
+
+        assert!(board.is_placement_phase());
+        assert!(referee.find_all_valid_moves(&board, player, &mut cells));
+        assert!(cells.iter().all(|cell| board.centre().contains(&cell)));
+
+        let next_move = cells.get(0);
+        referee.find_flip_cells_for_move(&board, player, next_move, &mut cells);
+        assert_eq!(cells.count, 0);
+        Referee::apply_move(&mut board, player, next_move, &cells);
+        player = player.opponent();
+    }
+
+    assert!(!board.is_placement_phase());
+    assert_eq!(board.supply(Player::Black), Some(6));
+    assert_eq!(board.supply(Player::White), Some(6));
+
+    // a player out of discs plays on with the opponent's
+    let mut exhausted = Board::new_reversi(4);
+    for _ in 0..8 {
+        exhausted.take_disc(Player::Black);
+    }
+    assert_eq!(exhausted.supply(Player::Black), Some(0));
+    assert!(exhausted.has_disc(Player::Black));
+    exhausted.take_disc(Player::Black);
+    assert_eq!(exhausted.supply(Player::White), Some(7));
+
+    for _ in 0..7 {
+        exhausted.take_disc(Player::White);
+    }
+    assert!(!exhausted.has_disc(Player::Black));
+    assert!(!referee.find_all_valid_moves(&exhausted, Player::Black, &mut cells));
+}
+
+#[test]
+fn torus_lines_wrap_around_the_edges() {
+
+    let mut referee = Referee::default();
+    let mut flip_cells = CellList::default();
+
+    let mut board = Board::from_text("
+        ----
+        O-XO
+        ----
+        ----").unwrap();
+
+    // the line west of the move only closes across the edge
+    assert!(!referee.validate_move(&board, Player::Black, (1, 1)));
+    board.topology = Topology::Torus;
+    assert!(referee.find_flip_cells_for_move(&board, Player::Black, (1, 1), &mut flip_cells));
+    assert_eq!(flip_cells.iter().collect::<Vec<_>>(), [(1, 3), (1, 0)]);
+
+    // both diagonals run through the cell opposite the move, which is flipped once
+    let mut board = Board::from_text("
+        ----
+        -O-O
+        --O-
+        -X-X").unwrap();
+    board.topology = Topology::Torus;
+    assert!(referee.find_flip_cells_for_move(&board, Player::Black, (0, 0), &mut flip_cells));
+    let mut flips = flip_cells.iter().collect::<Vec<_>>();
+    flips.sort();
+    assert_eq!(flips, [(1, 1), (1, 3), (2, 2)]);
+
+    // and no corner is worth more than any other cell
+    assert!(evaluation::classify(&board, (0, 0)) == evaluation::Region::Inner);
+}
+
+#[test]
+fn a_pass_is_only_valid_without_a_move() {
+
+    let mut referee = Referee::default();
+    let mut actions = Vec::new();
+
+    let board = Board::from_text("
+        OX--
+        ----
+        ----
+        ----").unwrap();
+
+    // black has nothing to capture, but white does
+    assert!(referee.find_all_valid_actions(&board, Player::Black, &mut actions));
+    assert_eq!(actions, [Action::Pass]);
+    assert!(referee.validate_action(&board, Player::Black, Action::Pass));
+
+    assert!(referee.find_all_valid_actions(&board, Player::White, &mut actions));
+    assert_eq!(actions, [Action::Place((0, 2))]);
+    assert!(!referee.validate_action(&board, Player::White, Action::Pass));
+
+    // nobody passes in a finished game
+    let finished = Board::from_text("
+        XXXX
+        XXXX
+        XXXX
+        XXX-").unwrap();
+
+    assert!(!referee.find_all_valid_actions(&finished, Player::White, &mut actions));
+    assert!(!referee.validate_action(&finished, Player::White, Action::Pass));
+}
+
+#[test]
+fn positions_read_back_the_same_as_text() {
+
+    let start = "---------------------------OX------XO--------------------------- X";
+    assert_eq!(Board::default().to_position_text(Player::Black), start);
+
+    let (board, player) = Board::from_position_text(start).unwrap();
+    assert_eq!(board.grid, Board::default().grid);
+    assert_eq!(player, Player::Black);
+
+    // random positions, with holes, on every size
+    let mut rng = StdRng::seed_from_u64(49);
+    let cells = [Cell::Empty, Cell::Taken(Player::Black), Cell::Taken(Player::White), Cell::Blocked];
+    for size in (Board::MIN_SIZE..=Board::MAX_SIZE).step_by(2) {
+
+        let mut board = Board::empty(size);
+        for row in 0..size {
+            for col in 0..size {
+
+                board.grid[row][col] = cells[rng.random_range(..cells.len())];
+            }
+        }
+
+        for player in [Player::Black, Player::White] {
+
+            let text = board.to_position_text(player);
+            let (read_board, read_player) = Board::from_position_text(&text).unwrap();
+            assert_eq!(read_board.grid, board.grid, "{text}");
+            assert_eq!(read_player, player, "{text}");
+        }
+    }
+
+    assert!(Board::from_position_text(&start[..start.len() - 1]).is_none());
+    assert!(Board::from_position_text("---------------------------OX------XO--------------------------- ?").is_none());
+}
+
+#[test]
+fn rolit_positions_read_back_the_same_as_text() {
+
+    let start = Board::new_rolit(8, 4);
+    assert_eq!(start.to_position_text(Player::Red), "---------------------------XO------BR--------------------------- R");
+
+    let (read_board, read_player) = Board::from_position_text(&start.to_position_text(Player::Black)).unwrap();
+    assert_eq!(read_board.grid, start.grid);
+    assert_eq!(read_board.player_count(), 4);
+    assert_eq!(read_player, Player::Black);
+
+    for (board, player, _action) in common::random_game(&start, Player::Black, 49) {
+
+        let text = board.to_position_text(player);
+        let (read_board, read_player) = Board::from_position_text(&text).unwrap();
+        assert_eq!(read_board.grid, board.grid, "{text}");
+        assert!(read_board.is_rolit(), "{text}");
+        assert_eq!(read_player, player, "{text}");
+        assert_eq!(read_board.to_position_text(read_player), text);
+    }
+
+    // without blue discs, blue only takes part if it's to move
+    let (board, player) = Board::from_position_text("---------------------------XO------OR--------------------------- r").unwrap();
+    assert_eq!(board.player_count(), 3);
+    assert_eq!(player, Player::Red);
+    let (board, player) = Board::from_position_text("---------------------------XO------OR--------------------------- B").unwrap();
+    assert_eq!(board.player_count(), 4);
+    assert_eq!(player, Player::Blue);
+}
+
+#[test]
+fn boards_print_as_a_lettered_grid() {
+
+    let board = Board::new(4);
+    assert_eq!(board.to_string(), "  a b c d\n1 - - - -\n2 - O X -\n3 - X O -\n4 - - - -\n");
+
+    let printed = Board::new(10).to_string();
+    assert!(printed.starts_with("   a b c d e f g h i j\n 1 - -"), "{printed}");
+    assert!(printed.ends_with("\n10 - - - - - - - - - -\n"), "{printed}");
+}
@@ -0,0 +1,41 @@
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::common::CellList;
+use othello::referee::Referee;
+
+// Helpers shared by the tests
+
+// plays random moves from the given position until the game is over, the same moves for the same seed
+// returns every turn as (the board before it, the player, the action), with the forced passes,
+// but without the passes of a finished game
+pub fn random_game(start: &Board, first_player: Player, seed: u64) -> Vec<(Board, Player, Action)> {
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut referee = Referee::default();
+    let mut actions = Vec::new();
+    let mut flip_cells = CellList::default();
+
+    let mut board = start.clone();
+    let mut player = first_player;
+    let mut turns = Vec::new();
+
+    while referee.find_all_valid_actions(&board, player, &mut actions) {
+
+        let action = actions[rng.random_range(..actions.len())];
+        turns.push((board.clone(), player, action));
+
+        if let Action::Place(next_move) = action {
+
+            referee.find_flip_cells_for_move(&board, player, next_move, &mut flip_cells);
+            Referee::apply_move(&mut board, player, next_move, &flip_cells);
+        }
+        player = board.next_player(player);
+    }
+
+    turns
+}
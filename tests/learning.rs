@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::PathBuf;
+
+use othello::board::Board;
+use othello::board::Player;
+use othello::common::CellList;
+use othello::evaluation;
+use othello::learning::Checkpoint;
+use othello::learning::Trainer;
+use othello::learning::TrainingConfig;
+use othello::referee::Referee;
+
+// a directory of its own for every test, removed again when the test is done
+struct TemporaryDirectory(PathBuf);
+
+impl TemporaryDirectory {
+
+    fn new(name: &str) -> Self {
+
+        let path = std::env::temp_dir().join(format!("othello-{name}-{}", std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+        TemporaryDirectory(path)
+    }
+}
+
+impl Drop for TemporaryDirectory {
+
+    fn drop(&mut self) {
+
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn checkpoints_read_back_the_same() {
+
+    let directory = TemporaryDirectory::new("checkpoint");
+    let path = directory.0.join("weights.txt");
+
+    let checkpoint = Checkpoint { weights: [1.5, -0.25, 30.0, -12.125, 0.0, 3.0], game_count: 1234 };
+    checkpoint.save(&path).unwrap();
+    assert_eq!(Checkpoint::load(&path).unwrap(), checkpoint);
+
+    // saving again replaces the checkpoint, without leaving anything on the side
+    let checkpoint = Checkpoint { game_count: 1235, ..checkpoint };
+    checkpoint.save(&path).unwrap();
+    assert_eq!(Checkpoint::load(&path).unwrap(), checkpoint);
+    assert_eq!(fs::read_dir(&directory.0).unwrap().count(), 1);
+
+    fs::write(&path, "games 3\nNo Such Feature 1.0\n").unwrap();
+    assert!(Checkpoint::load(&path).is_err());
+}
+
+#[test]
+fn training_moves_the_prediction_towards_the_outcome() {
+
+    // a game of a single move, where black takes the last cell and every disc with it
+    let start = Board::from_text("
+        -OXXXXXX
+        XXXXXXXX
+        XXXXXXXX
+        XXXXXXXX
+        XXXXXXXX
+        XXXXXXXX
+        XXXXXXXX
+        XXXXXXXX
+    ").unwrap();
+
+    // without any weights, every position is predicted to be a draw
+    let checkpoint = Checkpoint { weights: [0.0; evaluation::FEATURE_COUNT], game_count: 0 };
+    let config = TrainingConfig { exploration_rate: 0.0, ..TrainingConfig::default() };
+    let mut trainer = Trainer::new(checkpoint, config, 34);
+
+    assert_eq!(trainer.train_game_from(start.clone(), Player::Black), 64);
+    assert_eq!(trainer.checkpoint.game_count, 1);
+
+    let features = evaluation::extract_features(&mut Referee::default(), &mut CellList::default(), &start, Player::Black);
+    assert!(evaluation::evaluate(&features, &trainer.checkpoint.weights) > 0.0);
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use othello::agent::SearchStatistics;
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::evaluation;
+use othello::minimax;
+use othello::statistics::SearchStatistic;
+use othello::statistics::Statistics;
+use othello::strategy::RequestKind;
+use othello::strategy::SearchContext;
+
+#[test]
+fn analysis_ranks_the_best_lines() {
+
+    let mut search = SearchContext::headless(26);
+    let board = Board::default();
+
+    for recursion_depth in 1..=3 {
+
+        let all_lines = minimax::analyze(&mut search, &board, Player::Black, recursion_depth, 10, &evaluation::DEFAULT_WEIGHTS);
+        assert_eq!(all_lines.len(), 4);
+        assert!(all_lines.windows(2).all(|pair| pair[0].score >= pair[1].score));
+
+        for line in &all_lines {
+
+            assert_eq!(line.principal_variation.first(), Some(&Action::Place(line.next_move)));
+            assert_eq!(line.principal_variation.len(), recursion_depth);
+        }
+
+        // the best ones of the same lines
+        let lines = minimax::analyze(&mut search, &board, Player::Black, recursion_depth, 2, &evaluation::DEFAULT_WEIGHTS);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines.iter().map(|line| line.score).collect::<Vec<_>>(), all_lines[..2].iter().map(|line| line.score).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn searches_report_their_statistics() {
+
+    let mut search = SearchContext::headless(35);
+    search.begin_request(RequestKind::Move, 1, None);
+
+    let Action::Place(next_move) = minimax::find_best_move(&mut search, &Board::default(), Player::Black, 3, &evaluation::DEFAULT_WEIGHTS) else {
+        panic!("there is a valid move to play");
+    };
+    let statistics = search.statistics();
+
+    assert_eq!(statistics.depth, 3);
+    assert!(statistics.node_count >= 4 + 12);
+    assert!(statistics.score.is_some_and(f32::is_finite));
+    assert_eq!(statistics.principal_variation.first(), Some(&Action::Place(next_move)));
+    assert_eq!(statistics.principal_variation.len(), 3);
+
+    // a new request starts counting over
+    search.begin_request(RequestKind::Move, 2, None);
+    assert_eq!(search.statistics().node_count, 0);
+    assert_eq!(search.statistics().depth, 0);
+}
+
+#[test]
+fn search_statistics_add_up_per_player() {
+
+    let statistics = |node_count, milliseconds, depth| SearchStatistics {
+        node_count,
+        duration: Duration::from_millis(milliseconds),
+        depth,
+        score: None,
+        principal_variation: Vec::new(),
+    };
+
+    let mut total = SearchStatistic::default();
+    total.add(&statistics(1000, 500, 2));
+    total.add(&statistics(3000, 1500, 4));
+    assert_eq!(total.to_string(), "2 moves, depth 3.0, 2000 nodes/move, 1.000 s/move, 2000 nodes/s");
+
+    let mut all_statistics = Statistics::default();
+    all_statistics.add_search_datum("Minimax lvl 2".to_string(), &statistics(1000, 500, 2));
+    all_statistics.add_search_datum("Minimax lvl 2".to_string(), &statistics(3000, 1500, 4));
+    all_statistics.add_search_datum("Random".to_string(), &statistics(0, 0, 0));
+    assert_eq!(all_statistics.search_data["Minimax lvl 2"].to_string(), total.to_string());
+    assert_eq!(all_statistics.search_data["Random"].to_string(), "1 moves, depth 0.0, 0 nodes/move, 0.000 s/move, 0 nodes/s");
+}
+
+#[test]
+fn a_forced_pass_is_scored_like_a_move() {
+
+    // after black takes the a8 corner, white has to pass, and black moves again
+    let board = Board::from_text("
+        XOOO----
+        --------
+        --------
+        --------
+        --------
+        --------
+        --------
+        -OX-----
+    ").unwrap();
+
+    let mut search = SearchContext::headless(35);
+    for recursion_depth in 1..=4 {
+
+        let lines = minimax::analyze(&mut search, &board, Player::Black, recursion_depth, 10, &evaluation::DEFAULT_WEIGHTS);
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.score.is_finite()), "depth {recursion_depth}");
+    }
+
+    minimax::find_best_move(&mut search, &board, Player::Black, 4, &evaluation::DEFAULT_WEIGHTS);
+    assert!(search.statistics().score.is_some_and(f32::is_finite));
+
+    // the pass shows in the line, so that the moves keep alternating between the sides
+    let lines = minimax::analyze(&mut search, &board, Player::Black, 3, 10, &evaluation::DEFAULT_WEIGHTS);
+    let corner_line = lines.iter().find(|line| line.next_move == (7, 0)).unwrap();
+    assert_eq!(corner_line.principal_variation, [Action::Place((7, 0)), Action::Pass, Action::Place((0, 4))]);
+}
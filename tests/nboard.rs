@@ -0,0 +1,126 @@
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::time::Duration;
+use std::time::Instant;
+
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::board::Ruleset;
+use othello::common::CellList;
+use othello::nboard;
+use othello::nboard::ExternalEngineStrategy;
+use othello::referee::Referee;
+use othello::strategy::Parameter;
+use othello::strategy::RequestKind;
+use othello::strategy::SearchContext;
+use othello::strategy::StopFlags;
+use othello::strategy::Strategy;
+
+fn engine_parameters(command_line: &str, board_sizes: &str) -> Vec<Parameter> {
+
+    vec![
+        Parameter::text("Command Line", command_line),
+        Parameter::integer("Depth", 1, 1, 60),
+        Parameter::text("Board Sizes", board_sizes),
+    ]
+}
+
+fn stub_engine_parameters() -> Vec<Parameter> {
+
+    engine_parameters(env!("CARGO_BIN_EXE_stub_engine"), "8")
+}
+
+fn search_context() -> SearchContext {
+
+    SearchContext::headless(0)
+}
+
+#[test]
+fn stub_engine_plays_first_valid_move() {
+
+    let mut strategy = ExternalEngineStrategy::default();
+    let mut search = search_context();
+
+    let action = strategy.choose_move(&mut search, &Board::default(), Player::Black, &stub_engine_parameters());
+
+    assert_eq!(action, Action::Place((2, 3)));
+}
+
+#[test]
+fn stub_engine_plays_full_game() {
+
+    let mut strategy = ExternalEngineStrategy::default();
+    let mut search = search_context();
+    let mut referee = Referee::default();
+    let mut flip_cells = CellList::default();
+    let parameters = stub_engine_parameters();
+
+    let mut board = Board::default();
+    let mut player = Player::Black;
+    let mut move_count = 0;
+
+    loop {
+
+        if !referee.find_all_valid_moves(&board, player, &mut CellList::default()) {
+
+            player = player.opponent();
+            if !referee.find_all_valid_moves(&board, player, &mut CellList::default()) {
+                break;
+            }
+        }
+
+        let Action::Place(next_move) = strategy.choose_move(&mut search, &board, player, &parameters) else {
+            panic!("the engine passed with a valid move to play");
+        };
+        assert!(referee.find_flip_cells_for_move(&board, player, next_move, &mut flip_cells), "invalid move {next_move:?}");
+        Referee::apply_move(&mut board, player, next_move, &flip_cells);
+
+        player = player.opponent();
+        move_count += 1;
+    }
+
+    assert!(move_count > 0);
+}
+
+#[test]
+fn engine_only_gets_positions_it_can_play() {
+
+    let mut strategy = ExternalEngineStrategy::default();
+    let mut search = search_context();
+
+    // the GGF format has no blocked cells
+    let mut board = Board::default();
+    board.cut_corners(2);
+    assert_eq!(nboard::format_game(&board, Player::Black), None);
+    assert_eq!(strategy.choose_move(&mut search, &board, Player::Black, &stub_engine_parameters()), Action::Pass);
+
+    // other board sizes only if the engine is said to play them
+    let board = Board::new(6);
+    assert_eq!(strategy.choose_move(&mut search, &board, Player::Black, &stub_engine_parameters()), Action::Pass);
+    let parameters = engine_parameters(env!("CARGO_BIN_EXE_stub_engine"), "6 8");
+    assert!(matches!(strategy.choose_move(&mut search, &board, Player::Black, &parameters), Action::Place(_)));
+
+    // the engine would play for the most discs under misère rules too
+    let mut board = Board::default();
+    board.ruleset = Ruleset::Misere;
+    assert_eq!(strategy.choose_move(&mut search, &board, Player::Black, &stub_engine_parameters()), Action::Pass);
+}
+
+#[test]
+fn stopping_gives_up_on_an_engine_that_never_replies() {
+
+    let mut strategy = ExternalEngineStrategy::default();
+    let (response_sender, _response_receiver) = mpsc::channel();
+    let stop_flags = Arc::new(StopFlags::default());
+    let mut search = SearchContext::new(response_sender, stop_flags.clone(), 0);
+
+    search.begin_request(RequestKind::Move, 1, None);
+    stop_flags.stop(RequestKind::Move, 1);
+
+    let start = Instant::now();
+    let action = strategy.choose_move(&mut search, &Board::default(), Player::Black, &engine_parameters(concat!(env!("CARGO_BIN_EXE_stub_engine"), " --hang"), "8"));
+
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert_eq!(action, Action::Place((2, 3)));
+}
@@ -0,0 +1,72 @@
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::notation;
+use othello::record::GameRecord;
+use othello::record::Turn;
+
+mod common;
+
+#[test]
+fn every_cell_reads_back_the_same() {
+
+    for size in [Board::STANDARD_SIZE, Board::MAX_SIZE] {
+        for row in 0..size {
+            for col in 0..size {
+
+                let text = notation::format_move((row, col));
+                assert_eq!(notation::parse_move(&text, size), Some((row, col)), "{text}");
+                assert_eq!(notation::parse_move(&text.to_uppercase(), size), Some((row, col)), "{text}");
+            }
+        }
+    }
+
+    assert_eq!(notation::format_move((4, 5)), "f5");
+    assert_eq!(notation::format_move((15, 15)), "p16");
+    assert_eq!(notation::parse_move("i1", 8), None);
+    assert_eq!(notation::parse_move("a9", 8), None);
+    assert_eq!(notation::parse_move("a0", 8), None);
+    assert_eq!(notation::parse_move("5f", 8), None);
+}
+
+#[test]
+fn transcripts_report_the_first_bad_move() {
+
+    let mut record = GameRecord::new(Board::default(), Player::Black, 0);
+    assert_eq!(notation::play_transcript(&mut record, "F5 d6 C3"), Ok(()));
+    assert_eq!(notation::format_transcript(record.turns()), "f5d6c3");
+
+    let mut record = GameRecord::new(Board::default(), Player::Black, 0);
+    assert_eq!(notation::play_transcript(&mut record, "f5d6c3z9"), Err("Move 4 (z9) is not a cell of the board".to_string()));
+    assert_eq!(record.move_count(), 3);
+
+    let mut record = GameRecord::new(Board::default(), Player::Black, 0);
+    assert_eq!(notation::play_transcript(&mut record, "f5f5"), Err("Move 2 (f5) is not valid for White".to_string()));
+    assert_eq!(record.move_count(), 1);
+}
+
+#[test]
+fn transcripts_replay_games_with_passes() {
+
+    let mut pass_count = 0;
+
+    for seed in 0..20 {
+
+        let mut record = GameRecord::new(Board::default(), Player::Black, 0);
+        for (_board, player, action) in common::random_game(&Board::default(), Player::Black, seed) {
+
+            record.push(Turn { player, action });
+            if action == Action::Pass {
+                pass_count += 1;
+            }
+        }
+
+        // the passes are left out of the transcript, and made again when it's played
+        let transcript = notation::format_transcript(record.turns());
+        let mut replayed = GameRecord::new(Board::default(), Player::Black, 0);
+        assert_eq!(notation::play_transcript(&mut replayed, &transcript), Ok(()));
+        assert_eq!(replayed.turns(), record.turns(), "{transcript}");
+    }
+
+    assert!(pass_count > 0);
+}
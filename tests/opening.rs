@@ -0,0 +1,58 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use othello::board::Board;
+use othello::board::Cell;
+use othello::board::Player;
+use othello::opening;
+use othello::opening::StartingPosition;
+use othello::referee::Referee;
+
+#[test]
+fn every_xot_opening_is_valid() {
+
+    for line in opening::xot_openings() {
+
+        let (board, player) = opening::play_opening(line).unwrap_or_else(|e| panic!("invalid opening {line}: {e}"));
+
+        // eight moves played, and neither color wiped out
+        let (black_count, white_count) = Referee::count_disks(&board, Player::Black);
+        assert_eq!(black_count + white_count, 12, "{line}");
+        assert!(black_count > 0 && white_count > 0, "{line}");
+        assert_eq!(player, Player::Black, "{line}");
+    }
+}
+
+#[test]
+fn position_text_matches_the_standard_start() {
+
+    let text = "
+        --------
+        --------
+        --------
+        ---OX---
+        ---XO---
+        --------
+        --------
+        --------";
+
+    let mut rng = StdRng::seed_from_u64(39);
+    let (board, player) = opening::set_up(&StartingPosition::Text(text.to_string()), 8, &mut rng).unwrap();
+
+    assert_eq!(board.grid, Board::default().grid);
+    assert_eq!(player, Player::Black);
+
+    assert!(Board::from_text("---OX---").is_none());
+    assert!(Board::from_text(&text.replace('X', "Y")).is_none());
+}
+
+#[test]
+fn parallel_start_puts_each_color_side_by_side() {
+
+    let board = Board::new_parallel(8);
+
+    assert_eq!(board.cell((3, 3)), Cell::Taken(Player::White));
+    assert_eq!(board.cell((3, 4)), Cell::Taken(Player::White));
+    assert_eq!(board.cell((4, 3)), Cell::Taken(Player::Black));
+    assert_eq!(board.cell((4, 4)), Cell::Taken(Player::Black));
+}
@@ -0,0 +1,42 @@
+use othello::board::Board;
+use othello::board::Player;
+use othello::perft;
+use othello::referee::Referee;
+use othello::referee::Representation;
+
+// the well-known leaf counts from the standard start position, starting at depth 1
+const START_POSITION_COUNTS: [u64; 9] = [4, 12, 56, 244, 1396, 8200, 55092, 390216, 3005288];
+
+fn assert_start_position_counts(representation: Representation, max_depth: usize) {
+
+    let mut referee = Referee::with_representation(representation);
+    let board = Board::default();
+
+    for depth in 1..=max_depth {
+
+        assert_eq!(perft::perft(&mut referee, &board, Player::Black, depth), START_POSITION_COUNTS[depth - 1], "depth {depth}");
+    }
+}
+
+#[test]
+fn bitboard_matches_start_position_counts() {
+
+    assert_start_position_counts(Representation::BitBoard, 9);
+}
+
+#[test]
+fn grid_matches_start_position_counts() {
+
+    assert_start_position_counts(Representation::Grid, 7);
+}
+
+#[test]
+fn divide_adds_up_to_perft() {
+
+    let mut referee = Referee::default();
+    let board = Board::default();
+
+    let divided = perft::divide(&mut referee, &board, Player::Black, 5);
+    assert_eq!(divided.len(), 4);
+    assert_eq!(divided.iter().map(|(_, leaf_count)| leaf_count).sum::<u64>(), START_POSITION_COUNTS[4]);
+}
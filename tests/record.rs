@@ -0,0 +1,79 @@
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::common::CellList;
+use othello::record::GameRecord;
+use othello::record::Turn;
+use othello::referee::Referee;
+
+mod common;
+
+// plays a random game to the end, the way the GUI records it,
+// and returns the board before every move along with the record
+fn play_random_game(seed: u64) -> (Vec<(Board, Player)>, GameRecord) {
+
+    let mut record = GameRecord::new(Board::default(), Player::Black, seed);
+    let mut positions = Vec::new();
+
+    for (board, player, action) in common::random_game(&Board::default(), Player::Black, seed) {
+
+        if action != Action::Pass {
+
+            positions.push((board, player));
+        }
+        record.push(Turn { player, action });
+    }
+
+    (positions, record)
+}
+
+#[test]
+fn undo_takes_back_both_sides_against_the_ai() {
+
+    let mut referee = Referee::default();
+    let (positions, mut record) = play_random_game(46);
+    let (final_board, _) = record.replay(&mut referee);
+
+    // black is the human, white the AI
+    let is_human = |player: Player| player == Player::Black;
+    let black_positions: Vec<_> = positions.iter().filter(|(_, player)| *player == Player::Black).collect();
+
+    for (board, player) in black_positions.iter().rev() {
+
+        assert!(record.undo(is_human));
+        let (undone_board, undone_player) = record.replay(&mut referee);
+        assert_eq!(undone_board.grid, board.grid);
+        assert_eq!(undone_player, *player);
+    }
+    assert!(!record.can_undo(is_human));
+
+    for (board, _) in black_positions.iter().skip(1) {
+
+        assert!(record.redo(is_human));
+        assert_eq!(record.replay(&mut referee).0.grid, board.grid);
+    }
+
+    assert!(record.redo(is_human));
+    assert!(!record.can_redo());
+    assert_eq!(record.replay(&mut referee).0.grid, final_board.grid);
+}
+
+#[test]
+fn a_new_move_forgets_the_undone_ones() {
+
+    let (_positions, mut record) = play_random_game(47);
+    let is_human = |_player: Player| true;
+
+    assert!(record.undo(is_human));
+    assert!(record.undo(is_human));
+    assert!(record.can_redo());
+
+    let mut referee = Referee::default();
+    let (board, player) = record.replay(&mut referee);
+    let mut valid_moves = CellList::default();
+    assert!(referee.find_all_valid_moves(&board, player, &mut valid_moves));
+
+    record.push(Turn { player, action: Action::Place(valid_moves.get(0)) });
+    assert!(!record.can_redo());
+    assert_eq!(record.undone_turns(), []);
+}
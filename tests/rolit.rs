@@ -0,0 +1,140 @@
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Cell;
+use othello::board::Player;
+use othello::common::CellList;
+use othello::minimax;
+use othello::referee::Referee;
+use othello::strategy::SearchContext;
+
+mod common;
+
+#[test]
+fn turns_rotate_through_the_players() {
+
+    let board = Board::new_rolit(8, 3);
+
+    assert_eq!(board.players().collect::<Vec<_>>(), [Player::Black, Player::White, Player::Red]);
+    assert_eq!(board.next_player(Player::White), Player::Red);
+    assert_eq!(board.next_player(Player::Red), Player::Black);
+
+    // the fourth color still starts on the board
+    assert_eq!(board.cell((4, 3)), Cell::Taken(Player::Blue));
+}
+
+#[test]
+fn placement_is_only_allowed_without_a_capture() {
+
+    let mut referee = Referee::default();
+    let mut cells = CellList::default();
+
+    // nobody can capture on a board with a single disc
+    let mut board = Board::new_rolit(6, 4);
+    board.grid = Board::from_text("
+        ------
+        ------
+        --X---
+        ------
+        ------
+        ------").unwrap().grid;
+
+    assert!(referee.find_all_valid_moves(&board, Player::White, &mut cells));
+    assert_eq!(cells.count, 8);
+    assert!(referee.find_flip_cells_for_move(&board, Player::White, (1, 1), &mut cells));
+    assert_eq!(cells.count, 0);
+    assert!(!referee.validate_move(&board, Player::White, (0, 0)));
+
+    // once white can capture, white has to
+    board.grid[2][3] = Cell::Taken(Player::White);
+    assert!(referee.find_all_valid_moves(&board, Player::White, &mut cells));
+    assert_eq!(cells.iter().collect::<Vec<_>>(), [(2, 1)]);
+    assert!(!referee.validate_move(&board, Player::White, (1, 1)));
+
+    // the line can be made up of several other colors
+    board.grid[2][1] = Cell::Taken(Player::Red);
+    assert!(referee.find_flip_cells_for_move(&board, Player::White, (2, 0), &mut cells));
+    assert_eq!(cells.count, 2);
+}
+
+#[test]
+fn a_reused_referee_agrees_with_a_fresh_one() {
+
+    // one referee goes through every position of a game, for every player, like a search does,
+    // and in between through a position where nobody can capture
+    let mut referee = Referee::default();
+    let mut moves = CellList::default();
+    let mut fresh_moves = CellList::default();
+
+    let mut lone_disc = Board::new_rolit(8, 4);
+    lone_disc.grid = Board::from_text(&"-".repeat(8 * 8).replacen('-', "X", 1)).unwrap().grid;
+
+    for (board, _player, _action) in common::random_game(&Board::new_rolit(8, 4), Player::Black, 41) {
+        for player in board.players() {
+
+            assert!(referee.find_all_valid_moves(&lone_disc, player, &mut moves));
+            assert_eq!(moves.count, 3);
+
+            let has_moves = referee.find_all_valid_moves(&board, player, &mut moves);
+            let fresh_has_moves = Referee::default().find_all_valid_moves(&board, player, &mut fresh_moves);
+            assert_eq!(has_moves, fresh_has_moves);
+            assert_eq!(moves.iter().collect::<Vec<_>>(), fresh_moves.iter().collect::<Vec<_>>(), "{}", board.to_position_text(player));
+
+            for (row, col) in fresh_moves.iter() {
+
+                let mut new_board = board.clone();
+                assert_eq!(referee.find_and_apply_next_valid_move(&mut new_board, player, (row, col)), Some((row, col)));
+                assert!(referee.validate_move(&board, player, (row, col)));
+            }
+        }
+    }
+}
+
+#[test]
+fn max_n_plays_a_whole_game() {
+
+    let mut search = SearchContext::headless(41);
+    let mut referee = Referee::default();
+    let mut flip_cells = CellList::default();
+
+    let mut board = Board::new_rolit(6, 4);
+    let mut player = Player::Black;
+
+    // Rolit has no passes, every turn fills a cell
+    for _ in 0..6 * 6 - 4 {
+
+        let Action::Place(next_move) = minimax::find_best_move(&mut search, &board, player, 2, &[1.0, 0.0, 0.0, 0.0, 0.0, 0.0]) else {
+            panic!("Rolit has no passes");
+        };
+        assert!(referee.find_flip_cells_for_move(&board, player, next_move, &mut flip_cells), "{next_move:?}");
+        Referee::apply_move(&mut board, player, next_move, &flip_cells);
+        player = board.next_player(player);
+    }
+
+    assert_eq!(Referee::count_disks_per_player(&board).iter().sum::<i32>(), 6 * 6);
+    assert!(!referee.find_all_valid_moves(&board, player, &mut flip_cells));
+}
+
+#[test]
+fn analysis_scores_the_last_move_like_the_search() {
+
+    // black fills the last cell, and nobody moves after that
+    let mut board = Board::new_rolit(4, 4);
+    board.grid = Board::from_text("
+        -XOR
+        BXOR
+        BXOR
+        BXOR").unwrap().grid;
+
+    let weights = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+    let mut search = SearchContext::headless(41);
+    for recursion_depth in 1..=3 {
+
+        let lines = minimax::analyze(&mut search, &board, Player::Black, recursion_depth, 10, &weights);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].next_move, (0, 0));
+        assert!(lines[0].score.is_finite(), "depth {recursion_depth}");
+
+        minimax::find_best_move(&mut search, &board, Player::Black, recursion_depth, &weights);
+        assert_eq!(search.statistics().score, Some(lines[0].score), "depth {recursion_depth}");
+    }
+}
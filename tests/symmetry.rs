@@ -0,0 +1,105 @@
+use othello::board::Action;
+use othello::board::Board;
+use othello::board::Player;
+use othello::common::CellList;
+use othello::referee::Referee;
+use othello::symmetry;
+use othello::symmetry::Symmetry;
+
+mod common;
+
+type Move = (usize, usize);
+
+// the positions of a random game before every move, with the player to move in each
+fn random_positions(seed: u64) -> Vec<(Board, Player)> {
+
+    common::random_game(&Board::default(), Player::Black, seed).into_iter()
+        .filter(|(_board, _player, action)| *action != Action::Pass)
+        .map(|(board, player, _action)| (board, player))
+        .collect()
+}
+
+fn sorted_valid_moves(referee: &mut Referee, board: &Board, player: Player) -> Vec<Move> {
+
+    let mut valid_moves = CellList::default();
+    referee.find_all_valid_moves(board, player, &mut valid_moves);
+    let mut moves: Vec<_> = valid_moves.iter().collect();
+    moves.sort();
+    moves
+}
+
+#[test]
+fn every_symmetry_is_undone_by_its_inverse() {
+
+    let (board, _) = random_positions(50).swap_remove(20);
+
+    for symmetry in Symmetry::ALL {
+
+        assert_eq!(symmetry.inverse().apply(&symmetry.apply(&board)).grid, board.grid, "{symmetry:?}");
+
+        for size in [Board::STANDARD_SIZE, 5] {
+            for row in 0..size {
+                for col in 0..size {
+
+                    let moved = symmetry.apply_to_move(size, (row, col));
+                    assert_eq!(symmetry.inverse().apply_to_move(size, moved), (row, col), "{symmetry:?}");
+                }
+            }
+        }
+    }
+
+    // the standard start is only symmetric under half of them
+    let start = Board::default();
+    let symmetric: Vec<_> = Symmetry::ALL.into_iter().filter(|symmetry| symmetry.apply(&start).grid == start.grid).collect();
+    assert_eq!(symmetric, [Symmetry::Identity, Symmetry::Rotate180, Symmetry::FlipDiagonal, Symmetry::FlipAntiDiagonal]);
+}
+
+#[test]
+fn valid_moves_transform_with_the_board() {
+
+    let mut referee = Referee::default();
+
+    for (board, player) in random_positions(51) {
+        for symmetry in Symmetry::ALL {
+
+            let transformed = symmetry.apply(&board);
+            let mut expected: Vec<_> = sorted_valid_moves(&mut referee, &board, player).into_iter()
+                .map(|next_move| symmetry.apply_to_move(board.size(), next_move))
+                .collect();
+            expected.sort();
+
+            assert_eq!(sorted_valid_moves(&mut referee, &transformed, player), expected, "{symmetry:?}");
+        }
+    }
+}
+
+#[test]
+fn symmetric_positions_share_a_canonical_board() {
+
+    let mut referee = Referee::default();
+
+    for (board, player) in random_positions(52) {
+
+        let (canonical, symmetry) = symmetry::canonical(&board);
+        assert_eq!(symmetry.apply(&board).grid, canonical.grid);
+
+        for other in Symmetry::ALL {
+
+            assert_eq!(symmetry::canonical(&other.apply(&board)).0.grid, canonical.grid, "{other:?}");
+        }
+
+        // the moves on the canonical board map back to the moves on the board
+        let mut mapped: Vec<_> = sorted_valid_moves(&mut referee, &canonical, player).into_iter()
+            .map(|next_move| symmetry::from_canonical(symmetry, board.size(), next_move))
+            .collect();
+        mapped.sort();
+        assert_eq!(mapped, sorted_valid_moves(&mut referee, &board, player));
+    }
+
+    // the four opening moves of black are all the same move
+    let openings: Vec<_> = ["d3", "c4", "f5", "e6"].iter().map(|text| {
+        let (board, _) = othello::opening::play_opening(text).unwrap();
+        symmetry::canonical(&board).0.grid
+    }).collect();
+    assert!(openings.iter().all(|grid| *grid == openings[0]));
+}